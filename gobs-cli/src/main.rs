@@ -5,6 +5,7 @@ use structopt::StructOpt;
 
 use dot_vox::load;
 use gobs::cubic_surface_extractor::extract_cubic_mesh;
+use gobs::palette::Palette;
 use gobs::raw_volume::RawVolume;
 use gobs::raw_volume_sampler::RawVolumeSampler;
 use gobs::region::Region;
@@ -37,16 +38,14 @@ fn main() -> std::io::Result<()> {
 
     let vox_file = load(&options.vox_file).unwrap();
 
-    let hex_palette = vox_file
-        .palette
+    let hex_palette = Palette::from_abgr(&vox_file.palette)
+        .colours()
         .iter()
-        .map(|rgba| {
-            let a = (rgba >> 24) as u8;
-            let b = (rgba >> 16) as u8;
-            let g = (rgba >> 8) as u8;
-            let r = *rgba as u8;
-
-            format!("{{\"r\":{},\"g\":{},\"b\":{},\"a\":{}}}", r, g, b, a)
+        .map(|c| {
+            format!(
+                "{{\"r\":{},\"g\":{},\"b\":{},\"a\":{}}}",
+                c.r, c.g, c.b, c.a
+            )
         })
         .collect::<Vec<String>>()
         .join(", ");
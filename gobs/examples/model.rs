@@ -6,6 +6,7 @@ mod support;
 
 use dot_vox::load_bytes;
 use gobs::cubic_surface_extractor::extract_cubic_mesh;
+use gobs::palette::Palette;
 use gobs::raw_volume::RawVolume;
 use gobs::raw_volume_sampler::RawVolumeSampler;
 use gobs::region::Region;
@@ -31,18 +32,7 @@ fn main() {
         );
         let mut volume: RawVolume<u32> = RawVolume::new(model_region.clone());
 
-        let palette: Vec<u32> = vox_file
-            .palette
-            .iter()
-            .map(|rgba| {
-                let a = (rgba >> 24) as u8;
-                let b = (rgba >> 16) as u8;
-                let g = (rgba >> 8) as u8;
-                let r = *rgba as u8;
-
-                ((a as u32) << 24) | ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
-            })
-            .collect();
+        let palette = Palette::from_abgr(&vox_file.palette);
 
         vox_model.voxels.iter().for_each(|voxel| {
             volume
@@ -50,7 +40,7 @@ fn main() {
                     voxel.x as i32,
                     voxel.y as i32,
                     voxel.z as i32,
-                    palette[voxel.i as usize],
+                    palette.get(voxel.i as usize).to_argb(),
                 )
                 .unwrap();
         });
@@ -169,7 +169,7 @@ where
         target
             .draw(
                 &vertex_buffer,
-                &glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList),
+                glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList),
                 &program,
                 &uniforms,
                 &params,
@@ -181,12 +181,11 @@ where
 
         // polling and handling the events received by the window
         for event in events {
-            match event {
-                glutin::event::Event::WindowEvent { event, .. } => match event {
+            if let glutin::event::Event::WindowEvent { event, .. } = event {
+                match event {
                     glutin::event::WindowEvent::CloseRequested => action = support::Action::Stop,
-                    ev => state.process_input(&ev),
-                },
-                _ => (),
+                    ev => state.process_input(ev),
+                }
             }
         }
 
@@ -205,11 +204,8 @@ impl State {
         let input = match *event {
             glutin::event::WindowEvent::KeyboardInput { input, .. } => input,
             glutin::event::WindowEvent::MouseWheel { delta, .. } => {
-                match delta {
-                    glutin::event::MouseScrollDelta::LineDelta(_x, y) => {
-                        self.r += y * 0.1;
-                    }
-                    _ => (),
+                if let glutin::event::MouseScrollDelta::LineDelta(_x, y) = delta {
+                    self.r += y * 0.1;
                 }
                 return;
             }
@@ -1,6 +1,5 @@
 #![allow(dead_code)]
 
-use glium;
 use glium::glutin::event::{Event, StartCause};
 use glium::glutin::event_loop::{ControlFlow, EventLoop};
 use std::time::{Duration, Instant};
@@ -81,10 +80,9 @@ where
     let mut next_frame_time = Instant::now();
     event_loop.run(move |event, _, control_flow| {
         let run_callback = match event.to_static() {
-            Some(Event::NewEvents(cause)) => match cause {
-                StartCause::ResumeTimeReached { .. } | StartCause::Init => true,
-                _ => false,
-            },
+            Some(Event::NewEvents(StartCause::ResumeTimeReached { .. }))
+            | Some(Event::NewEvents(StartCause::Init)) => true,
+            Some(Event::NewEvents(_)) => false,
             Some(event) => {
                 events_buffer.push(event);
                 false
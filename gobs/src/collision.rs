@@ -0,0 +1,103 @@
+use crate::cubic_surface_extractor::{
+    extract_cubic_mesh_custom, is_any_solid_quad_needed, CubicVertex,
+};
+use crate::mesh::{FaceArity, Mesh};
+use crate::region::Region;
+use crate::sampler::Sampler;
+use crate::volume::Volume;
+use crate::voxel::Voxel;
+use vek::vec3::Vec3;
+
+/// Covers the non-empty voxels of `region` with axis aligned boxes which do not overlap.
+///
+/// Boxes are grown greedily along x, then y, then z from the first voxel not yet covered, which
+/// gives far fewer boxes than voxels but not necessarily the fewest possible. Each box is returned
+/// as the region of voxels it covers.
+pub fn extract_boxes<T>(volume: &dyn Volume<T>, region: &Region) -> Vec<Region>
+where
+    T: Voxel,
+{
+    let region = match region.intersect(volume.get_region()) {
+        Some(region) => region,
+        None => return vec![],
+    };
+
+    let lower = region.get_lower_corner();
+    let width = region.get_width() as usize;
+    let height = region.get_height() as usize;
+    let index = |p: Vec3<i32>| {
+        let local = p - lower;
+        local.x as usize + local.y as usize * width + local.z as usize * width * height
+    };
+
+    let mut covered = vec![false; region.get_volume() as usize];
+    let is_free = |covered: &[bool], p: Vec3<i32>| {
+        !covered[index(p)] && !volume.get_voxel_at(p.x, p.y, p.z).is_empty()
+    };
+
+    let mut boxes = vec![];
+    for start in region.iter() {
+        if !is_free(&covered, start) {
+            continue;
+        }
+
+        let mut upper = start;
+        while upper.x < region.upper_x
+            && is_free(&covered, Vec3::new(upper.x + 1, start.y, start.z))
+        {
+            upper.x += 1;
+        }
+
+        let all_free = |covered: &[bool], r: Region| r.iter().all(|p| is_free(covered, p));
+        while upper.y < region.upper_y
+            && all_free(
+                &covered,
+                Region::new(
+                    Vec3::new(start.x, upper.y + 1, start.z),
+                    Vec3::new(upper.x, upper.y + 1, start.z),
+                ),
+            )
+        {
+            upper.y += 1;
+        }
+
+        while upper.z < region.upper_z
+            && all_free(
+                &covered,
+                Region::new(
+                    Vec3::new(start.x, start.y, upper.z + 1),
+                    Vec3::new(upper.x, upper.y, upper.z + 1),
+                ),
+            )
+        {
+            upper.z += 1;
+        }
+
+        let found = Region::new(start, upper);
+        for p in found.iter() {
+            covered[index(p)] = true;
+        }
+        boxes.push(found);
+    }
+
+    boxes
+}
+
+/// Extracts a triangle mesh of the surface of the non-empty voxels, for physics rather than
+/// rendering.
+///
+/// Materials are ignored, so faces are merged across them and every vertex carries the default
+/// voxel.
+pub fn extract_collision_mesh<T>(
+    sampler: &mut dyn Sampler<T>,
+    region: &Region,
+) -> Option<Mesh<CubicVertex<T>>>
+where
+    T: Voxel,
+{
+    let mut mesh: Mesh<CubicVertex<T>> = Mesh::new(FaceArity::Three);
+
+    extract_cubic_mesh_custom(sampler, region, &mut mesh, is_any_solid_quad_needed, true)?;
+
+    Some(mesh)
+}
@@ -0,0 +1,166 @@
+use crate::raw_volume::RawVolume;
+use crate::region::Region;
+use crate::volume::Volume;
+use crate::voxel::Voxel;
+use std::collections::VecDeque;
+use vek::vec3::Vec3;
+
+/// Which neighbours of a voxel count as touching it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Voxels sharing a face.
+    Six,
+    /// Voxels sharing a face or an edge.
+    Eighteen,
+    /// Voxels sharing a face, an edge or a corner.
+    TwentySix,
+}
+
+impl Connectivity {
+    pub fn offsets(self) -> Vec<Vec3<i32>> {
+        let max_axes = match self {
+            Connectivity::Six => 1,
+            Connectivity::Eighteen => 2,
+            Connectivity::TwentySix => 3,
+        };
+
+        Region::new(Vec3::broadcast(-1), Vec3::broadcast(1))
+            .iter()
+            .filter(|offset| {
+                let axes = offset.map(|c| (c != 0) as i32).sum();
+                axes > 0 && axes <= max_axes
+            })
+            .collect()
+    }
+}
+
+/// A group of connected non-empty voxels.
+#[derive(Clone, Debug)]
+pub struct Component {
+    pub label: u32,
+    pub region: Region,
+    pub voxel_count: usize,
+}
+
+/// The result of `label_components`.
+///
+/// `labels` holds the label of the component each voxel belongs to, or zero for empty voxels.
+/// Labels start from one, so the component with label `n` is `components[n - 1]`.
+pub struct ComponentLabels {
+    pub labels: RawVolume<u32>,
+    pub components: Vec<Component>,
+}
+
+/// Splits the non-empty voxels of `region` into connected components.
+pub fn label_components<T>(
+    volume: &dyn Volume<T>,
+    region: &Region,
+    connectivity: Connectivity,
+) -> ComponentLabels
+where
+    T: Voxel,
+{
+    let mut labels = RawVolume::new(region.clone());
+    let mut components = vec![];
+    let offsets = connectivity.offsets();
+    let mut queue = VecDeque::new();
+
+    for start in region.iter() {
+        if labels.get_voxel_at(start.x, start.y, start.z) != 0
+            || volume.get_voxel_at(start.x, start.y, start.z).is_empty()
+        {
+            continue;
+        }
+
+        let label = components.len() as u32 + 1;
+        let mut component = Component {
+            label,
+            region: Region::new(start, start),
+            voxel_count: 0,
+        };
+
+        labels
+            .set_voxel_at(start.x, start.y, start.z, label)
+            .unwrap();
+        queue.push_back(start);
+        while let Some(position) = queue.pop_front() {
+            component.voxel_count += 1;
+            component.region = Region::new(
+                Vec3::min(component.region.get_lower_corner(), position),
+                Vec3::max(component.region.get_upper_corner(), position),
+            );
+
+            for offset in offsets.iter() {
+                let next = position + *offset;
+                if region.contains_point(next.x, next.y, next.z)
+                    && labels.get_voxel_at(next.x, next.y, next.z) == 0
+                    && !volume.get_voxel_at(next.x, next.y, next.z).is_empty()
+                {
+                    labels.set_voxel_at(next.x, next.y, next.z, label).unwrap();
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        components.push(component);
+    }
+
+    ComponentLabels { labels, components }
+}
+
+impl ComponentLabels {
+    pub fn get_label(&self, x: i32, y: i32, z: i32) -> u32 {
+        self.labels.get_voxel_at(x, y, z)
+    }
+
+    pub fn get_component(&self, label: u32) -> Option<&Component> {
+        self.components.get((label as usize).checked_sub(1)?)
+    }
+
+    /// Finds the components which have no voxel at a position accepted by `is_anchor`.
+    ///
+    /// For example `|p| p.y == 0` finds everything not resting on the ground plane.
+    pub fn find_islands<F>(&self, is_anchor: F) -> Vec<&Component>
+    where
+        F: Fn(Vec3<i32>) -> bool,
+    {
+        let mut anchored = vec![false; self.components.len()];
+        for (position, label) in self.labels.iter_non_empty() {
+            if !anchored[label as usize - 1] && is_anchor(position) {
+                anchored[label as usize - 1] = true;
+            }
+        }
+
+        self.components
+            .iter()
+            .zip(anchored)
+            .filter(|(_, anchored)| !anchored)
+            .map(|(component, _)| component)
+            .collect()
+    }
+
+    /// Copies the voxels of one component out of `volume` into a volume covering just that
+    /// component. Returns `None` if there is no component with that label.
+    pub fn extract_component<T>(&self, volume: &dyn Volume<T>, label: u32) -> Option<RawVolume<T>>
+    where
+        T: Voxel,
+    {
+        let region = self.get_component(label)?.region.clone();
+        let mut result = RawVolume::new(region.clone());
+
+        for position in region.iter() {
+            if self.get_label(position.x, position.y, position.z) == label {
+                result
+                    .set_voxel_at(
+                        position.x,
+                        position.y,
+                        position.z,
+                        volume.get_voxel_at(position.x, position.y, position.z),
+                    )
+                    .unwrap();
+            }
+        }
+
+        Some(result)
+    }
+}
@@ -0,0 +1,134 @@
+use crate::raw_volume::RawVolume;
+use crate::region::Region;
+use crate::volume::{PositionError, Volume};
+use crate::voxel::Voxel;
+use vek::vec3::Vec3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CsgOperation {
+    /// Voxels which are non-empty in either volume.
+    Union,
+    /// Voxels which are non-empty in both volumes.
+    Intersection,
+    /// Voxels which are non-empty in the first volume but not the second.
+    Difference,
+}
+
+/// How to pick the material of a voxel which is non-empty in both volumes.
+pub enum ConflictPolicy<T> {
+    KeepA,
+    KeepB,
+    /// Calls the closure with the voxels from the first and second volume.
+    Custom(Box<dyn Fn(T, T) -> T>),
+}
+
+impl<T> ConflictPolicy<T>
+where
+    T: Voxel,
+{
+    fn resolve(&self, a: T, b: T) -> T {
+        match self {
+            ConflictPolicy::KeepA => a,
+            ConflictPolicy::KeepB => b,
+            ConflictPolicy::Custom(f) => f(a, b),
+        }
+    }
+}
+
+fn combine_voxels<T>(a: T, b: T, operation: CsgOperation, policy: &ConflictPolicy<T>) -> T
+where
+    T: Voxel,
+{
+    match operation {
+        CsgOperation::Union if a.is_empty() => b,
+        CsgOperation::Union if b.is_empty() => a,
+        CsgOperation::Intersection if a.is_empty() || b.is_empty() => Default::default(),
+        CsgOperation::Difference if b.is_empty() => a,
+        CsgOperation::Difference => Default::default(),
+        _ => policy.resolve(a, b),
+    }
+}
+
+/// Reads a voxel, treating everything outside the volume's region as empty rather than the border
+/// value.
+fn get_voxel<T>(volume: &dyn Volume<T>, p: Vec3<i32>) -> T
+where
+    T: Voxel,
+{
+    if volume.get_region().contains_point(p.x, p.y, p.z) {
+        volume.get_voxel_at(p.x, p.y, p.z)
+    } else {
+        Default::default()
+    }
+}
+
+/// Combines `a` with `b` moved by `offset` into a new volume covering both of them.
+///
+/// The voxel of `b` at `p` lines up with the voxel of `a` at `p + offset`.
+pub fn combine<T>(
+    a: &dyn Volume<T>,
+    b: &dyn Volume<T>,
+    offset: Vec3<i32>,
+    operation: CsgOperation,
+    policy: &ConflictPolicy<T>,
+) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    let (region_a, region_b) = (a.get_region(), b.get_region());
+    let region = Region::new(
+        Vec3::min(
+            region_a.get_lower_corner(),
+            region_b.get_lower_corner() + offset,
+        ),
+        Vec3::max(
+            region_a.get_upper_corner(),
+            region_b.get_upper_corner() + offset,
+        ),
+    );
+
+    let mut result = RawVolume::new(region);
+    for (p, voxel) in result.iter_mut() {
+        *voxel = combine_voxels(get_voxel(a, p), get_voxel(b, p - offset), operation, policy);
+    }
+
+    result
+}
+
+/// Like `combine`, but writes the result into `a`. Only the voxels inside the region of `a` can
+/// change.
+pub fn combine_in_place<T>(
+    a: &mut dyn Volume<T>,
+    b: &dyn Volume<T>,
+    offset: Vec3<i32>,
+    operation: CsgOperation,
+    policy: &ConflictPolicy<T>,
+) -> Result<(), PositionError>
+where
+    T: Voxel,
+{
+    let region_b = b.get_region();
+    let shifted = Region::new(
+        region_b.get_lower_corner() + offset,
+        region_b.get_upper_corner() + offset,
+    );
+
+    // outside of `b` only an intersection changes anything
+    let region = match operation {
+        CsgOperation::Intersection => a.get_region().clone(),
+        _ => match a.get_region().intersect(&shifted) {
+            Some(region) => region,
+            None => return Ok(()),
+        },
+    };
+
+    for p in region.iter() {
+        let old = a.get_voxel_at(p.x, p.y, p.z);
+        let new = combine_voxels(old, get_voxel(b, p - offset), operation, policy);
+        if new != old {
+            a.set_voxel_at(p.x, p.y, p.z, new)?;
+        }
+    }
+
+    Ok(())
+}
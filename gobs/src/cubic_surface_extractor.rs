@@ -1,13 +1,16 @@
+use vek::vec2::Vec2;
 use vek::vec3::Vec3;
 
 use crate::mesh::{FaceArity, Mesh};
 use crate::region::Region;
 use crate::sampler::Sampler;
+use crate::texture_atlas::TextureAtlas;
 use crate::voxel::Voxel;
 use std::fmt::{Debug, Formatter};
 
 const MAX_VERTICES_PER_POSITION: usize = 8;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CubicVertex<T>
 where
     T: Voxel,
@@ -34,18 +37,164 @@ where
 {
     pub fn new(x: u8, y: u8, z: u8, data: T) -> Self {
         CubicVertex {
-            position: x as u32 + (y as u32 * 0x100) + (z as u32 * 0x10000),
+            position: encode_position(x, y, z),
             data,
         }
     }
 
     pub fn decode(&self) -> Vec3<u8> {
-        Vec3 {
-            x: (self.position & 0xff) as u8,
-            y: ((self.position >> 8) & 0xff) as u8,
-            z: ((self.position >> 16) & 0xff) as u8,
+        decode_position(self.position)
+    }
+}
+
+/// A cubic vertex which also carries the face it belongs to and its texture coordinates.
+///
+/// `uv` is measured in voxel faces, so it runs past 1.0 on merged quads. Use
+/// `TextureAtlas::get_atlas_uv` (or the equivalent in a shader) to wrap it into `tile`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TexturedCubicVertex<T>
+where
+    T: Voxel,
+{
+    pub position: u32,
+    pub face: Face,
+    pub uv: Vec2<f32>,
+    pub tile: u32,
+    pub data: T,
+}
+
+impl<T> Debug for TexturedCubicVertex<T>
+where
+    T: Voxel,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TexturedCubicVertex")
+            .field("position", &self.decode())
+            .field("face", &self.face)
+            .field("uv", &self.uv)
+            .field("tile", &self.tile)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+impl<T> TexturedCubicVertex<T>
+where
+    T: Voxel,
+{
+    pub fn new(x: u8, y: u8, z: u8, face: Face, uv: Vec2<f32>, tile: u32, data: T) -> Self {
+        TexturedCubicVertex {
+            position: encode_position(x, y, z),
+            face,
+            uv,
+            tile,
+            data,
         }
     }
+
+    pub fn decode(&self) -> Vec3<u8> {
+        decode_position(self.position)
+    }
+}
+
+/// A cubic vertex which also carries the light level in front of the face it belongs to.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LitCubicVertex<T>
+where
+    T: Voxel,
+{
+    pub position: u32,
+    pub light: u8,
+    pub data: T,
+}
+
+impl<T> Debug for LitCubicVertex<T>
+where
+    T: Voxel,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LitCubicVertex")
+            .field("position", &self.decode())
+            .field("light", &self.light)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+impl<T> LitCubicVertex<T>
+where
+    T: Voxel,
+{
+    pub fn new(x: u8, y: u8, z: u8, light: u8, data: T) -> Self {
+        LitCubicVertex {
+            position: encode_position(x, y, z),
+            light,
+            data,
+        }
+    }
+
+    pub fn decode(&self) -> Vec3<u8> {
+        decode_position(self.position)
+    }
+}
+
+fn encode_position(x: u8, y: u8, z: u8) -> u32 {
+    x as u32 + (y as u32 * 0x100) + (z as u32 * 0x10000)
+}
+
+fn decode_position(position: u32) -> Vec3<u8> {
+    Vec3 {
+        x: (position & 0xff) as u8,
+        y: ((position >> 8) & 0xff) as u8,
+        z: ((position >> 16) & 0xff) as u8,
+    }
+}
+
+/// The direction a face of a cubic mesh is pointing in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Face {
+    PositiveX,
+    NegativeX,
+    PositiveY,
+    NegativeY,
+    PositiveZ,
+    NegativeZ,
+}
+
+impl Face {
+    pub fn normal(self) -> Vec3<i32> {
+        match self {
+            Face::PositiveX => Vec3::new(1, 0, 0),
+            Face::NegativeX => Vec3::new(-1, 0, 0),
+            Face::PositiveY => Vec3::new(0, 1, 0),
+            Face::NegativeY => Vec3::new(0, -1, 0),
+            Face::PositiveZ => Vec3::new(0, 0, 1),
+            Face::NegativeZ => Vec3::new(0, 0, -1),
+        }
+    }
+
+    /// Projects a corner of a quad spanning `lower` to `upper` onto the plane of this face.
+    ///
+    /// The result is oriented so textures appear upright and unmirrored when the face is viewed
+    /// from outside. Side faces use +y as up, the top face uses -z and the bottom face +z.
+    pub fn texture_coordinates(
+        self,
+        position: Vec3<i32>,
+        lower: Vec3<i32>,
+        upper: Vec3<i32>,
+    ) -> Vec2<f32> {
+        let (u, v) = match self {
+            Face::PositiveX => (upper.z - position.z, position.y - lower.y),
+            Face::NegativeX => (position.z - lower.z, position.y - lower.y),
+            Face::PositiveY => (position.x - lower.x, upper.z - position.z),
+            Face::NegativeY => (position.x - lower.x, position.z - lower.z),
+            Face::PositiveZ => (position.x - lower.x, position.y - lower.y),
+            Face::NegativeZ => (upper.x - position.x, position.y - lower.y),
+        };
+
+        Vec2::new(u as f32, v as f32)
+    }
 }
 
 #[derive(Default, Clone, Debug)]
@@ -135,12 +284,10 @@ where
 {
     pub data: Vec<T>,
     pub width: usize,
-    pub height: usize,
-    pub depth: usize,
     pub area: usize,
 }
 
-impl<'a, T> Array3<T>
+impl<T> Array3<T>
 where
     T: Default + Clone,
 {
@@ -148,8 +295,6 @@ where
         Array3 {
             data: vec![Default::default(); width * height * depth],
             width,
-            height,
-            depth,
             area: width * height,
         }
     }
@@ -209,25 +354,25 @@ where
                     if a.maybe_merge(b, mesh) {
                         b.merged = true;
                         merge_found = true;
-                        len = len - 1;
+                        len -= 1;
                     }
                 }
             }
         }
-        i = i + 1;
+        i += 1;
         quads.retain(|quad| !quad.merged);
     }
 
     merge_found
 }
 
-pub fn extract_cubic_mesh_custom<T, F>(
+fn extract_quads<T, F>(
     sampler: &mut dyn Sampler<T>,
     region: &Region,
     mesh: &mut Mesh<CubicVertex<T>>,
     is_quad_needed: F,
     merge_quads: bool,
-) -> Option<bool>
+) -> Option<Vec<(Face, Vec<Quad>)>>
 where
     T: Voxel,
     F: Fn(&T, &T) -> Option<T>,
@@ -261,9 +406,9 @@ where
                 let reg_x = (x - region.lower_x) as u32;
 
                 let current_voxel = sampler.get_voxel();
-                let neg_x_voxel = sampler.peek_voxel_1nx0py0pz();
-                let neg_y_voxel = sampler.peek_voxel_0px1ny0pz();
-                let neg_z_voxel = sampler.peek_voxel_0px0py1nz();
+                let neg_x_voxel = sampler.peek(-1, 0, 0);
+                let neg_y_voxel = sampler.peek(0, -1, 0);
+                let neg_z_voxel = sampler.peek(0, 0, -1);
 
                 // X
                 if let Some(material) = is_quad_needed(&current_voxel, &neg_x_voxel) {
@@ -513,30 +658,62 @@ where
             .for_each(|item| item.index = -1)
     }
 
-    for face in vec![
-        pos_x_quads,
-        neg_x_quads,
-        pos_y_quads,
-        neg_y_quads,
-        pos_z_quads,
-        neg_z_quads,
+    let mut result = vec![];
+    for (face, slices) in [
+        (Face::PositiveX, pos_x_quads),
+        (Face::NegativeX, neg_x_quads),
+        (Face::PositiveY, pos_y_quads),
+        (Face::NegativeY, neg_y_quads),
+        (Face::PositiveZ, pos_z_quads),
+        (Face::NegativeZ, neg_z_quads),
     ] {
-        for mut quads in face {
+        for mut quads in slices {
             if merge_quads {
-                while perform_quad_merging(&mut quads, &mesh) {}
+                while perform_quad_merging(&mut quads, mesh) {}
             }
 
-            match mesh.face_arity() {
-                FaceArity::Three => {
-                    for quad in quads {
-                        mesh.add_triangle(quad.v0, quad.v1, quad.v2);
-                        mesh.add_triangle(quad.v0, quad.v2, quad.v3);
-                    }
+            result.push((face, quads));
+        }
+    }
+
+    Some(result)
+}
+
+fn is_solid_quad_needed<T>(back: &T, front: &T) -> Option<T>
+where
+    T: Voxel,
+{
+    if !back.is_empty() && front.is_empty() {
+        Some(*back)
+    } else {
+        None
+    }
+}
+
+pub fn extract_cubic_mesh_custom<T, F>(
+    sampler: &mut dyn Sampler<T>,
+    region: &Region,
+    mesh: &mut Mesh<CubicVertex<T>>,
+    is_quad_needed: F,
+    merge_quads: bool,
+) -> Option<bool>
+where
+    T: Voxel,
+    F: Fn(&T, &T) -> Option<T>,
+{
+    let faces = extract_quads(sampler, region, mesh, is_quad_needed, merge_quads)?;
+
+    for (_, quads) in faces {
+        match mesh.face_arity() {
+            FaceArity::Three => {
+                for quad in quads {
+                    mesh.add_triangle(quad.v0, quad.v1, quad.v2);
+                    mesh.add_triangle(quad.v0, quad.v2, quad.v3);
                 }
-                FaceArity::Four => {
-                    for quad in quads {
-                        mesh.add_quad(quad.v0, quad.v1, quad.v2, quad.v3)
-                    }
+            }
+            FaceArity::Four => {
+                for quad in quads {
+                    mesh.add_quad(quad.v0, quad.v1, quad.v2, quad.v3)
                 }
             }
         }
@@ -563,15 +740,277 @@ where
         sampler,
         region,
         &mut mesh,
-        |back, front| {
-            if !back.is_empty() && front.is_empty() {
-                Some(*back)
-            } else {
-                None
+        is_solid_quad_needed,
+        merge_quads.unwrap_or(true),
+    )?;
+
+    Some(mesh)
+}
+
+/// Extracts a cubic mesh where every quad gets its own four vertices carrying texture coordinates.
+///
+/// The texture coordinates of a merged quad span the whole merged rectangle in voxel units, so a
+/// texture repeats once per voxel face. The atlas tile for each quad is looked up from its
+/// material and face direction.
+pub fn extract_textured_cubic_mesh_custom<T, F>(
+    sampler: &mut dyn Sampler<T>,
+    region: &Region,
+    mesh: &mut Mesh<TexturedCubicVertex<T>>,
+    atlas: &TextureAtlas<T>,
+    is_quad_needed: F,
+    merge_quads: bool,
+) -> Option<bool>
+where
+    T: Voxel,
+    F: Fn(&T, &T) -> Option<T>,
+{
+    mesh.clear();
+
+    let mut cubic_mesh: Mesh<CubicVertex<T>> = Mesh::new(FaceArity::Four);
+    let faces = extract_quads(
+        sampler,
+        region,
+        &mut cubic_mesh,
+        is_quad_needed,
+        merge_quads,
+    )?;
+
+    for (face, quads) in faces {
+        for quad in quads {
+            let corners = [quad.v0, quad.v1, quad.v2, quad.v3].map(|index| {
+                let vertex = &cubic_mesh.vertices[index as usize];
+                (vertex.decode().map(|c| c as i32), vertex.data)
+            });
+
+            let lower = corners
+                .iter()
+                .fold(corners[0].0, |acc, (position, _)| Vec3::min(acc, *position));
+            let upper = corners
+                .iter()
+                .fold(corners[0].0, |acc, (position, _)| Vec3::max(acc, *position));
+
+            let material = corners[0].1;
+            let tile = atlas.get_tile(material, face);
+
+            let indices = corners.map(|(position, _)| {
+                mesh.add_vertex(TexturedCubicVertex::new(
+                    position.x as u8,
+                    position.y as u8,
+                    position.z as u8,
+                    face,
+                    face.texture_coordinates(position, lower, upper),
+                    tile,
+                    material,
+                )) as i32
+            });
+
+            match mesh.face_arity() {
+                FaceArity::Three => {
+                    mesh.add_triangle(indices[0], indices[1], indices[2]);
+                    mesh.add_triangle(indices[0], indices[2], indices[3]);
+                }
+                FaceArity::Four => mesh.add_quad(indices[0], indices[1], indices[2], indices[3]),
             }
-        },
+        }
+    }
+
+    mesh.set_offset(region.get_lower_corner());
+
+    Some(true)
+}
+
+pub fn extract_textured_cubic_mesh<T>(
+    sampler: &mut dyn Sampler<T>,
+    region: &Region,
+    atlas: &TextureAtlas<T>,
+    face_arity: Option<FaceArity>,
+    merge_quads: Option<bool>,
+) -> Option<Mesh<TexturedCubicVertex<T>>>
+where
+    T: Voxel,
+{
+    let mut mesh: Mesh<TexturedCubicVertex<T>> = Mesh::new(face_arity.unwrap_or(FaceArity::Three));
+
+    extract_textured_cubic_mesh_custom(
+        sampler,
+        region,
+        &mut mesh,
+        atlas,
+        is_solid_quad_needed,
         merge_quads.unwrap_or(true),
     )?;
 
     Some(mesh)
 }
+
+/// Extracts a cubic mesh with the light level of the voxel in front of each face baked into its
+/// vertices.
+///
+/// `light` gives the light level at a position in the volume, for example from
+/// `LightVolume::get_light`. Quads are never merged, as merged quads would smear the light of one
+/// voxel across their whole area, and every quad gets its own four vertices.
+pub fn extract_lit_cubic_mesh<T, F>(
+    sampler: &mut dyn Sampler<T>,
+    region: &Region,
+    light: F,
+    face_arity: Option<FaceArity>,
+) -> Option<Mesh<LitCubicVertex<T>>>
+where
+    T: Voxel,
+    F: Fn(i32, i32, i32) -> u8,
+{
+    let mut mesh: Mesh<LitCubicVertex<T>> = Mesh::new(face_arity.unwrap_or(FaceArity::Three));
+
+    let mut cubic_mesh: Mesh<CubicVertex<T>> = Mesh::new(FaceArity::Four);
+    let faces = extract_quads(
+        sampler,
+        region,
+        &mut cubic_mesh,
+        is_solid_quad_needed,
+        false,
+    )?;
+
+    let region_lower = region.get_lower_corner();
+    for (face, quads) in faces {
+        let normal = face.normal();
+        // faces pointing along a negative axis lie on the far side of the voxel in front of them
+        let in_front = if normal.sum() < 0 {
+            normal
+        } else {
+            Vec3::zero()
+        };
+
+        for quad in quads {
+            let corners = [quad.v0, quad.v1, quad.v2, quad.v3].map(|index| {
+                let vertex = &cubic_mesh.vertices[index as usize];
+                (vertex.decode(), vertex.data)
+            });
+
+            let lower = corners
+                .iter()
+                .fold(corners[0].0, |acc, (position, _)| Vec3::min(acc, *position));
+            let front = region_lower + lower.map(|c| c as i32) + in_front;
+            let level = light(front.x, front.y, front.z);
+
+            let indices = corners.map(|(position, data)| {
+                mesh.add_vertex(LitCubicVertex::new(
+                    position.x, position.y, position.z, level, data,
+                )) as i32
+            });
+
+            match mesh.face_arity() {
+                FaceArity::Three => {
+                    mesh.add_triangle(indices[0], indices[1], indices[2]);
+                    mesh.add_triangle(indices[0], indices[2], indices[3]);
+                }
+                FaceArity::Four => mesh.add_quad(indices[0], indices[1], indices[2], indices[3]),
+            }
+        }
+    }
+
+    mesh.set_offset(region_lower);
+
+    Some(mesh)
+}
+
+/// The materials under one quad of a mesh from `extract_material_agnostic_cubic_mesh`.
+///
+/// `materials` holds the voxel behind each voxel face the quad covers, in rows of `width`. The
+/// entry at `u + v * width` is the one under the texture coordinates `u..u + 1, v..v + 1` that
+/// `Face::texture_coordinates` gives for the corners of the quad.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuadMaterials<T>
+where
+    T: Voxel,
+{
+    pub face: Face,
+    pub width: u32,
+    pub height: u32,
+    pub materials: Vec<T>,
+}
+
+/// A material agnostic cubic mesh along with the materials under each of its quads.
+pub type MaterialAgnosticMesh<T> = (Mesh<CubicVertex<T>>, Vec<QuadMaterials<T>>);
+
+pub(crate) fn is_any_solid_quad_needed<T>(back: &T, front: &T) -> Option<T>
+where
+    T: Voxel,
+{
+    if !back.is_empty() && front.is_empty() {
+        Some(Default::default())
+    } else {
+        None
+    }
+}
+
+/// Extracts a cubic mesh with quads merged regardless of their material, for shadow and collision
+/// meshes, or for rendering with the materials looked up from a texture.
+///
+/// The vertices all carry the default voxel. Entry `i` of the returned list holds the materials
+/// under quad `i`, which becomes triangles `2i` and `2i + 1` in a triangle mesh.
+pub fn extract_material_agnostic_cubic_mesh<T>(
+    sampler: &mut dyn Sampler<T>,
+    region: &Region,
+    face_arity: Option<FaceArity>,
+) -> Option<MaterialAgnosticMesh<T>>
+where
+    T: Voxel,
+{
+    let mut mesh: Mesh<CubicVertex<T>> = Mesh::new(face_arity.unwrap_or(FaceArity::Three));
+    let faces = extract_quads(sampler, region, &mut mesh, is_any_solid_quad_needed, true)?;
+
+    let region_lower = region.get_lower_corner();
+    let mut result = vec![];
+    for (face, quads) in faces {
+        let normal = face.normal();
+        let in_plane = Vec3::one() - normal.map(i32::abs);
+        // faces pointing along a positive axis lie on the far side of their voxel
+        let behind = if normal.sum() > 0 {
+            -normal
+        } else {
+            Vec3::zero()
+        };
+
+        for quad in quads {
+            let indices = [quad.v0, quad.v1, quad.v2, quad.v3];
+            let corners = indices.map(|i| mesh.vertices[i as usize].decode().map(|c| c as i32));
+            let lower = corners.iter().fold(corners[0], |a, b| Vec3::min(a, *b));
+            let upper = corners.iter().fold(corners[0], |a, b| Vec3::max(a, *b));
+
+            let size = face.texture_coordinates(upper, lower, upper)
+                - face.texture_coordinates(lower, lower, upper);
+            let (width, height) = (size.x.abs() as u32, size.y.abs() as u32);
+
+            let mut materials = vec![Default::default(); (width * height) as usize];
+            for position in Region::new(lower, upper - in_plane).iter() {
+                let near = face.texture_coordinates(position, lower, upper);
+                let far = face.texture_coordinates(position + in_plane, lower, upper);
+                let texel = near.x.min(far.x) as u32 + near.y.min(far.y) as u32 * width;
+
+                let voxel = region_lower + position + behind;
+                sampler.set_position(voxel.x, voxel.y, voxel.z);
+                materials[texel as usize] = sampler.get_voxel();
+            }
+
+            result.push(QuadMaterials {
+                face,
+                width,
+                height,
+                materials,
+            });
+
+            match mesh.face_arity() {
+                FaceArity::Three => {
+                    mesh.add_triangle(indices[0], indices[1], indices[2]);
+                    mesh.add_triangle(indices[0], indices[2], indices[3]);
+                }
+                FaceArity::Four => mesh.add_quad(indices[0], indices[1], indices[2], indices[3]),
+            }
+        }
+    }
+
+    mesh.set_offset(region_lower);
+    mesh.remove_unused_vertices();
+
+    Some((mesh, result))
+}
@@ -0,0 +1,137 @@
+use crate::cubic_surface_extractor::CubicVertex;
+use crate::mesh::{FaceArity, Mesh};
+use crate::voxel::Voxel;
+use std::collections::{HashMap, HashSet};
+use vek::vec3::Vec3;
+
+fn is_degenerate(a: Vec3<i32>, b: Vec3<i32>, c: Vec3<i32>) -> bool {
+    (b - a).cross(c - b) == Vec3::zero()
+}
+
+/// Tests if `p` lies on the segment from `a` to `b`, strictly between its ends.
+fn is_between(a: Vec3<i32>, b: Vec3<i32>, p: Vec3<i32>) -> bool {
+    p != a && p != b && is_degenerate(a, p, b) && (p - a).dot(p - b) < 0
+}
+
+/// Splits a convex polygon, which may have extra vertices along its edges, into triangles without
+/// adding any zero area triangles.
+fn triangulate<T>(polygon: &mut Vec<(i32, Vec3<i32>)>, mesh: &mut Mesh<CubicVertex<T>>)
+where
+    T: Voxel,
+{
+    while polygon.len() >= 3 {
+        let count = polygon.len();
+        let ear = (0..count).find(|i| {
+            let prev = polygon[(i + count - 1) % count].1;
+            let next = polygon[(i + 1) % count].1;
+            // cutting the ear adds an edge from `prev` to `next`, which must not run past any of
+            // the other corners, or it would leave a T-junction behind
+            !is_degenerate(prev, polygon[*i].1, next)
+                && !polygon.iter().any(|(_, p)| is_between(prev, next, *p))
+        });
+
+        let i = match ear {
+            Some(i) => i,
+            // whatever is left has no area
+            None => break,
+        };
+
+        mesh.add_triangle(
+            polygon[(i + count - 1) % count].0,
+            polygon[i].0,
+            polygon[(i + 1) % count].0,
+        );
+        polygon.remove(i);
+    }
+}
+
+/// Splits the faces of a cubic mesh at every vertex lying on one of their edges.
+///
+/// Where a large merged quad meets several smaller ones, the corners of the small quads fall
+/// part way along an edge of the large one. Rounding differences when rendering can then open
+/// up cracks along that edge. The result always has triangle faces, because the split faces may
+/// have more than four corners.
+pub fn remove_t_junctions<T>(mesh: &Mesh<CubicVertex<T>>) -> Mesh<CubicVertex<T>>
+where
+    T: Voxel,
+{
+    let arity = match mesh.face_arity() {
+        FaceArity::Three => 3,
+        FaceArity::Four => 4,
+    };
+
+    let mut result: Mesh<CubicVertex<T>> = Mesh::new(FaceArity::Three);
+    result.set_offset(mesh.offset);
+
+    let mut by_position: HashMap<Vec3<i32>, Vec<i32>> = HashMap::new();
+    for vertex in mesh.vertices() {
+        let index = result.add_vertex(CubicVertex {
+            position: vertex.position,
+            data: vertex.data,
+        }) as i32;
+        by_position
+            .entry(vertex.decode().map(|c| c as i32))
+            .or_default()
+            .push(index);
+    }
+
+    let used: HashSet<Vec3<i32>> = mesh
+        .indices()
+        .iter()
+        .map(|i| mesh.vertices()[*i as usize].decode().map(|c| c as i32))
+        .collect();
+
+    let mut polygon = vec![];
+    for face in mesh.indices().chunks(arity) {
+        let data = mesh.vertices()[face[0] as usize].data;
+        polygon.clear();
+
+        for (i, index) in face.iter().enumerate() {
+            let start = result.vertices[*index as usize].decode().map(|c| c as i32);
+            let end = result.vertices[face[(i + 1) % arity] as usize]
+                .decode()
+                .map(|c| c as i32);
+            polygon.push((*index, start));
+
+            // only edges along an axis can have other vertices on them, as the diagonals of
+            // triangulated quads run across the inside of the quad
+            let delta = end - start;
+            if delta.map(|c| (c != 0) as i32).sum() != 1 {
+                continue;
+            }
+
+            let step = delta.map(i32::signum);
+            let mut position = start + step;
+            while position != end {
+                if used.contains(&position) {
+                    let indices = by_position.entry(position).or_default();
+                    let existing = indices
+                        .iter()
+                        .copied()
+                        .find(|i| result.vertices[*i as usize].data == data);
+                    let index = match existing {
+                        Some(index) => index,
+                        None => {
+                            let index = result.add_vertex(CubicVertex::new(
+                                position.x as u8,
+                                position.y as u8,
+                                position.z as u8,
+                                data,
+                            )) as i32;
+                            indices.push(index);
+                            index
+                        }
+                    };
+                    polygon.push((index, position));
+                }
+                position += step;
+            }
+        }
+
+        triangulate(&mut polygon, &mut result);
+    }
+
+    result.remove_unused_vertices();
+
+    result
+}
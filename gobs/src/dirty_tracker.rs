@@ -0,0 +1,144 @@
+use crate::cubic_surface_extractor::{extract_cubic_mesh, CubicVertex};
+use crate::mesh::{FaceArity, Mesh};
+use crate::region::Region;
+use crate::sampler::Sampler;
+use crate::volume::Volume;
+use crate::voxel::Voxel;
+use std::collections::HashSet;
+use vek::vec3::Vec3;
+
+/// A chunk position paired with the mesh extracted for it.
+pub type ChunkMesh<T> = (Vec3<i32>, Mesh<CubicVertex<T>>);
+
+/// Keeps track of which chunks of a volume need their meshes regenerated.
+///
+/// A volume is split into cubic chunks of `chunk_size` voxels along each side, with chunk
+/// `(0, 0, 0)` starting at the origin. An edit on the upper border of a chunk also dirties the
+/// chunks on the other side of that border. The cubic extractor compares each voxel with its
+/// neighbours on the negative side, so a chunk's mesh also depends on the voxels just below its
+/// lower borders.
+pub struct DirtyTracker {
+    chunk_size: i32,
+    dirty: HashSet<Vec3<i32>>,
+}
+
+impl DirtyTracker {
+    pub fn new(chunk_size: i32) -> Self {
+        assert!(chunk_size > 0);
+
+        DirtyTracker {
+            chunk_size,
+            dirty: HashSet::new(),
+        }
+    }
+
+    pub fn get_chunk_size(&self) -> i32 {
+        self.chunk_size
+    }
+
+    pub fn get_chunk_containing(&self, x: i32, y: i32, z: i32) -> Vec3<i32> {
+        Vec3::new(
+            x.div_euclid(self.chunk_size),
+            y.div_euclid(self.chunk_size),
+            z.div_euclid(self.chunk_size),
+        )
+    }
+
+    pub fn get_chunk_region(&self, chunk: Vec3<i32>) -> Region {
+        let lower = chunk * self.chunk_size;
+
+        Region::new(lower, lower + (self.chunk_size - 1))
+    }
+
+    fn get_affected_chunks(&self, coordinate: i32) -> (i32, i32) {
+        let chunk = coordinate.div_euclid(self.chunk_size);
+        match coordinate.rem_euclid(self.chunk_size) {
+            local if local == self.chunk_size - 1 => (chunk, chunk + 1),
+            _ => (chunk, chunk),
+        }
+    }
+
+    pub fn mark_modified(&mut self, x: i32, y: i32, z: i32) {
+        let (lower_x, upper_x) = self.get_affected_chunks(x);
+        let (lower_y, upper_y) = self.get_affected_chunks(y);
+        let (lower_z, upper_z) = self.get_affected_chunks(z);
+
+        for cz in lower_z..=upper_z {
+            for cy in lower_y..=upper_y {
+                for cx in lower_x..=upper_x {
+                    self.dirty.insert(Vec3::new(cx, cy, cz));
+                }
+            }
+        }
+    }
+
+    /// Marks every chunk touched by `region`, along with the chunks past its upper borders.
+    pub fn mark_region_modified(&mut self, region: &Region) {
+        let (lower_x, _) = self.get_affected_chunks(region.lower_x);
+        let (lower_y, _) = self.get_affected_chunks(region.lower_y);
+        let (lower_z, _) = self.get_affected_chunks(region.lower_z);
+        let (_, upper_x) = self.get_affected_chunks(region.upper_x);
+        let (_, upper_y) = self.get_affected_chunks(region.upper_y);
+        let (_, upper_z) = self.get_affected_chunks(region.upper_z);
+
+        for cz in lower_z..=upper_z {
+            for cy in lower_y..=upper_y {
+                for cx in lower_x..=upper_x {
+                    self.dirty.insert(Vec3::new(cx, cy, cz));
+                }
+            }
+        }
+    }
+
+    /// Marks the chunks affected by every modification the volume has recorded.
+    pub fn mark_volume_modifications<T>(&mut self, volume: &mut dyn Volume<T>)
+    where
+        T: Voxel,
+    {
+        for position in volume.take_modified_positions() {
+            self.mark_modified(position.x, position.y, position.z);
+        }
+    }
+
+    pub fn is_dirty(&self, chunk: Vec3<i32>) -> bool {
+        self.dirty.contains(&chunk)
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.dirty.is_empty()
+    }
+
+    /// Returns the dirty chunks ordered by z, then y, then x and marks them as clean.
+    pub fn take_dirty_chunks(&mut self) -> Vec<Vec3<i32>> {
+        let mut chunks: Vec<Vec3<i32>> = self.dirty.drain().collect();
+        chunks.sort_by_key(|chunk| (chunk.z, chunk.y, chunk.x));
+
+        chunks
+    }
+}
+
+/// Regenerates the cubic meshes of all dirty chunks which overlap `bounds`.
+///
+/// Each chunk region is clipped to `bounds`. Dirty chunks outside of `bounds` are discarded. The
+/// result pairs each chunk with its new mesh.
+pub fn extract_dirty_cubic_meshes<T>(
+    tracker: &mut DirtyTracker,
+    sampler: &mut dyn Sampler<T>,
+    bounds: &Region,
+    face_arity: Option<FaceArity>,
+    merge_quads: Option<bool>,
+) -> Option<Vec<ChunkMesh<T>>>
+where
+    T: Voxel,
+{
+    let mut result = vec![];
+
+    for chunk in tracker.take_dirty_chunks() {
+        if let Some(region) = tracker.get_chunk_region(chunk).intersect(bounds) {
+            let mesh = extract_cubic_mesh(sampler, &region, face_arity, merge_quads)?;
+            result.push((chunk, mesh));
+        }
+    }
+
+    Some(result)
+}
@@ -0,0 +1,323 @@
+use crate::region::Region;
+use crate::sdf::Sdf;
+use crate::volume::Volume;
+use crate::voxel::Voxel;
+use vek::vec3::Vec3;
+
+/// The noise functions provided by `Noise`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NoiseKind {
+    Value,
+    Perlin,
+    Simplex,
+}
+
+/// Seeded 3D noise.
+///
+/// The output only depends on the seed and the position, so a world can be generated one chunk at
+/// a time, in any order, and the chunks will line up. All the noise functions return values in
+/// `-1.0..=1.0`.
+#[derive(Clone)]
+pub struct Noise {
+    permutation: [u8; 512],
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+    z ^ (z >> 31)
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+fn gradient(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = match h {
+        0..=3 => y,
+        12 | 14 => x,
+        _ => z,
+    };
+
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+const SIMPLEX_GRADIENTS: [(f64, f64, f64); 12] = [
+    (1.0, 1.0, 0.0),
+    (-1.0, 1.0, 0.0),
+    (1.0, -1.0, 0.0),
+    (-1.0, -1.0, 0.0),
+    (1.0, 0.0, 1.0),
+    (-1.0, 0.0, 1.0),
+    (1.0, 0.0, -1.0),
+    (-1.0, 0.0, -1.0),
+    (0.0, 1.0, 1.0),
+    (0.0, -1.0, 1.0),
+    (0.0, 1.0, -1.0),
+    (0.0, -1.0, -1.0),
+];
+
+impl Noise {
+    pub fn new(seed: u64) -> Self {
+        let mut table = [0u8; 256];
+        for (i, value) in table.iter_mut().enumerate() {
+            *value = i as u8;
+        }
+
+        let mut state = seed;
+        for i in (1..256).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+
+        let mut permutation = [0u8; 512];
+        for (i, value) in permutation.iter_mut().enumerate() {
+            *value = table[i & 255];
+        }
+
+        Noise { permutation }
+    }
+
+    fn hash(&self, x: i32, y: i32, z: i32) -> u8 {
+        let p = &self.permutation;
+        let h = p[(x & 255) as usize] as usize + (y & 255) as usize;
+        let h = p[h] as usize + (z & 255) as usize;
+
+        p[h]
+    }
+
+    /// Interpolates random values placed at the integer lattice points.
+    pub fn value(&self, position: Vec3<f64>) -> f64 {
+        let cell = position.map(|c| c.floor());
+        let (x, y, z) = (cell.x as i32, cell.y as i32, cell.z as i32);
+        let t = (position - cell).map(fade);
+
+        let corner =
+            |dx: i32, dy: i32, dz: i32| self.hash(x + dx, y + dy, z + dz) as f64 / 127.5 - 1.0;
+
+        lerp(
+            lerp(
+                lerp(corner(0, 0, 0), corner(1, 0, 0), t.x),
+                lerp(corner(0, 1, 0), corner(1, 1, 0), t.x),
+                t.y,
+            ),
+            lerp(
+                lerp(corner(0, 0, 1), corner(1, 0, 1), t.x),
+                lerp(corner(0, 1, 1), corner(1, 1, 1), t.x),
+                t.y,
+            ),
+            t.z,
+        )
+    }
+
+    /// Ken Perlin's improved gradient noise.
+    pub fn perlin(&self, position: Vec3<f64>) -> f64 {
+        let cell = position.map(|c| c.floor());
+        let (x, y, z) = (cell.x as i32, cell.y as i32, cell.z as i32);
+        let f = position - cell;
+        let t = f.map(fade);
+
+        let corner = |dx: i32, dy: i32, dz: i32| {
+            gradient(
+                self.hash(x + dx, y + dy, z + dz),
+                f.x - dx as f64,
+                f.y - dy as f64,
+                f.z - dz as f64,
+            )
+        };
+
+        let result = lerp(
+            lerp(
+                lerp(corner(0, 0, 0), corner(1, 0, 0), t.x),
+                lerp(corner(0, 1, 0), corner(1, 1, 0), t.x),
+                t.y,
+            ),
+            lerp(
+                lerp(corner(0, 0, 1), corner(1, 0, 1), t.x),
+                lerp(corner(0, 1, 1), corner(1, 1, 1), t.x),
+                t.y,
+            ),
+            t.z,
+        );
+
+        result.clamp(-1.0, 1.0)
+    }
+
+    /// Simplex noise, which has fewer directional artifacts than Perlin noise.
+    pub fn simplex(&self, position: Vec3<f64>) -> f64 {
+        const SKEW: f64 = 1.0 / 3.0;
+        const UNSKEW: f64 = 1.0 / 6.0;
+
+        let s = (position.x + position.y + position.z) * SKEW;
+        let cell = position.map(|c| (c + s).floor());
+        let t = (cell.x + cell.y + cell.z) * UNSKEW;
+        let d0 = position - (cell - t);
+
+        // work out which of the six tetrahedra in the skewed cube the position is in
+        let (o1, o2) = if d0.x >= d0.y {
+            if d0.y >= d0.z {
+                (Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0))
+            } else if d0.x >= d0.z {
+                (Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 1.0))
+            } else {
+                (Vec3::new(0.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 1.0))
+            }
+        } else if d0.y < d0.z {
+            (Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 1.0, 1.0))
+        } else if d0.x < d0.z {
+            (Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 1.0))
+        } else {
+            (Vec3::new(0.0, 1.0, 0.0), Vec3::new(1.0, 1.0, 0.0))
+        };
+
+        let (x, y, z) = (cell.x as i32, cell.y as i32, cell.z as i32);
+        let corners = [
+            (Vec3::zero(), d0),
+            (o1, d0 - o1 + UNSKEW),
+            (o2, d0 - o2 + 2.0 * UNSKEW),
+            (Vec3::one(), d0 - 1.0 + 3.0 * UNSKEW),
+        ];
+
+        let sum: f64 = corners
+            .iter()
+            .map(|(offset, d): &(Vec3<f64>, Vec3<f64>)| {
+                let falloff = 0.6 - d.magnitude_squared();
+                if falloff < 0.0 {
+                    return 0.0;
+                }
+
+                let hash = self.hash(
+                    x + offset.x as i32,
+                    y + offset.y as i32,
+                    z + offset.z as i32,
+                );
+                let (gx, gy, gz) = SIMPLEX_GRADIENTS[hash as usize % 12];
+                falloff.powi(4) * (gx * d.x + gy * d.y + gz * d.z)
+            })
+            .sum();
+
+        (32.0 * sum).clamp(-1.0, 1.0)
+    }
+
+    pub fn sample(&self, kind: NoiseKind, position: Vec3<f64>) -> f64 {
+        match kind {
+            NoiseKind::Value => self.value(position),
+            NoiseKind::Perlin => self.perlin(position),
+            NoiseKind::Simplex => self.simplex(position),
+        }
+    }
+
+    /// Sums `octaves` layers of noise, each with twice the frequency and half the amplitude of
+    /// the one before. The result is scaled back into `-1.0..=1.0`.
+    pub fn fractal(&self, kind: NoiseKind, position: Vec3<f64>, octaves: u32) -> f64 {
+        let mut sum = 0.0;
+        let mut amplitude = 1.0;
+        let mut total_amplitude = 0.0;
+        let mut frequency = 1.0;
+
+        for octave in 0..octaves {
+            // shift each octave so the lattice points of the layers do not line up
+            let offset = octave as f64 * 17.31;
+            sum += amplitude * self.sample(kind, position * frequency + offset);
+            total_amplitude += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+
+        if total_amplitude > 0.0 {
+            sum / total_amplitude
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Fills every voxel of `region` with the value `f` returns for its position.
+///
+/// Only the part of `region` inside the volume is written. As the value of a voxel only depends on
+/// its position, a large world can be generated by calling this once per chunk.
+pub fn generate<T, F>(volume: &mut dyn Volume<T>, region: &Region, f: F)
+where
+    T: Voxel,
+    F: Fn(Vec3<i32>) -> T,
+{
+    let region = match region.intersect(volume.get_region()) {
+        Some(region) => region,
+        None => return,
+    };
+
+    for position in region.iter() {
+        volume
+            .set_voxel_at(position.x, position.y, position.z, f(position))
+            .unwrap();
+    }
+}
+
+/// Fills `region` from a heightmap, with y pointing up.
+///
+/// `height` gives the height of the ground for each `(x, z)` column. Voxels below it are filled
+/// with the value `material` returns for their position and the depth below the surface, and the
+/// voxels above it are emptied.
+pub fn generate_heightmap<T, H, M>(
+    volume: &mut dyn Volume<T>,
+    region: &Region,
+    height: H,
+    material: M,
+) where
+    T: Voxel,
+    H: Fn(i32, i32) -> f64,
+    M: Fn(Vec3<i32>, f64) -> T,
+{
+    let region = match region.intersect(volume.get_region()) {
+        Some(region) => region,
+        None => return,
+    };
+
+    for z in region.lower_z..=region.upper_z {
+        for x in region.lower_x..=region.upper_x {
+            let ground = height(x, z);
+
+            for y in region.lower_y..=region.upper_y {
+                let position = Vec3::new(x, y, z);
+                let voxel = if (y as f64) < ground {
+                    material(position, ground - y as f64)
+                } else {
+                    Default::default()
+                };
+
+                volume.set_voxel_at(x, y, z, voxel).unwrap();
+            }
+        }
+    }
+}
+
+/// Writes `material` into the voxels of `region` whose centres are inside `shape`.
+///
+/// The other voxels are left alone, so shapes of different materials can be painted on top of
+/// each other, or on top of terrain from `generate_heightmap`.
+pub fn generate_sdf<T>(volume: &mut dyn Volume<T>, region: &Region, shape: &dyn Sdf, material: T)
+where
+    T: Voxel,
+{
+    let region = match region.intersect(volume.get_region()) {
+        Some(region) => region,
+        None => return,
+    };
+
+    for position in region.iter() {
+        if shape.distance(position.map(|c| c as f32 + 0.5)) <= 0.0 {
+            volume
+                .set_voxel_at(position.x, position.y, position.z, material)
+                .unwrap();
+        }
+    }
+}
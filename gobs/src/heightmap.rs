@@ -0,0 +1,277 @@
+use crate::generator::generate_heightmap;
+use crate::raw_volume::RawVolume;
+use crate::region::Region;
+use crate::voxel::Voxel;
+use std::io::{self, Read};
+use vek::vec3::Vec3;
+
+/// How the samples of a raw heightmap file are stored.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RawFormat {
+    U8,
+    U16LittleEndian,
+    U16BigEndian,
+}
+
+/// One band of material below the surface of the terrain.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Layer<T>
+where
+    T: Voxel,
+{
+    pub thickness: i32,
+    pub material: T,
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// A grid of ground heights, stored as they were imported.
+///
+/// Samples run along x first and then z, with y pointing up when the heightmap is turned into a
+/// volume. `max_value` is the sample value of the highest possible ground.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Heightmap {
+    width: usize,
+    depth: usize,
+    max_value: u16,
+    heights: Vec<u16>,
+}
+
+impl Heightmap {
+    pub fn new(width: usize, depth: usize, max_value: u16, heights: Vec<u16>) -> Self {
+        assert_eq!(heights.len(), width * depth);
+
+        Heightmap {
+            width,
+            depth,
+            max_value,
+            heights,
+        }
+    }
+
+    pub fn from_u8(width: usize, depth: usize, heights: &[u8]) -> Self {
+        Heightmap::new(
+            width,
+            depth,
+            u8::MAX as u16,
+            heights.iter().map(|h| *h as u16).collect(),
+        )
+    }
+
+    pub fn from_u16(width: usize, depth: usize, heights: &[u16]) -> Self {
+        Heightmap::new(width, depth, u16::MAX, heights.to_vec())
+    }
+
+    /// Reads a headerless file of `width * depth` samples.
+    pub fn read_raw(
+        reader: &mut dyn Read,
+        width: usize,
+        depth: usize,
+        format: RawFormat,
+    ) -> io::Result<Self> {
+        let sample_size = if format == RawFormat::U8 { 1 } else { 2 };
+        let size = width
+            .checked_mul(depth)
+            .and_then(|count| count.checked_mul(sample_size))
+            .ok_or_else(|| invalid_data("heightmap size is too large"))?;
+        // read rather than preallocate, so a bad size fails at the end of the data
+        let mut bytes = vec![];
+        reader.take(size as u64).read_to_end(&mut bytes)?;
+        if bytes.len() < size {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "missing heightmap samples",
+            ));
+        }
+
+        Ok(match format {
+            RawFormat::U8 => Heightmap::from_u8(width, depth, &bytes),
+            RawFormat::U16LittleEndian => Heightmap::new(
+                width,
+                depth,
+                u16::MAX,
+                bytes
+                    .chunks(2)
+                    .map(|b| u16::from_le_bytes([b[0], b[1]]))
+                    .collect(),
+            ),
+            RawFormat::U16BigEndian => Heightmap::new(
+                width,
+                depth,
+                u16::MAX,
+                bytes
+                    .chunks(2)
+                    .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                    .collect(),
+            ),
+        })
+    }
+
+    /// Reads a binary (`P5`) or plain text (`P2`) PGM image, with 8 or 16 bits per sample.
+    pub fn read_pgm(reader: &mut dyn Read) -> io::Result<Self> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+
+        let mut position = 0;
+        let mut next_token = || -> io::Result<String> {
+            loop {
+                match bytes.get(position) {
+                    Some(b'#') => {
+                        while position < bytes.len() && bytes[position] != b'\n' {
+                            position += 1;
+                        }
+                    }
+                    Some(c) if c.is_ascii_whitespace() => position += 1,
+                    Some(_) => break,
+                    None => return Err(invalid_data("unexpected end of PGM data")),
+                }
+            }
+
+            let start = position;
+            while position < bytes.len() && !bytes[position].is_ascii_whitespace() {
+                position += 1;
+            }
+
+            Ok(String::from_utf8_lossy(&bytes[start..position]).into_owned())
+        };
+
+        let magic = next_token()?;
+        let mut number = || -> io::Result<usize> {
+            next_token()?
+                .parse()
+                .map_err(|_| invalid_data("invalid number in PGM data"))
+        };
+        let width = number()?;
+        let depth = number()?;
+        let max_value = number()?;
+        if max_value == 0 || max_value > u16::MAX as usize {
+            return Err(invalid_data("invalid PGM maximum value"));
+        }
+
+        let count = width
+            .checked_mul(depth)
+            .ok_or_else(|| invalid_data("PGM size is too large"))?;
+        let heights = match magic.as_str() {
+            "P2" => (0..count)
+                .map(|_| number().map(|h| h.min(max_value) as u16))
+                .collect::<io::Result<Vec<u16>>>()?,
+            "P5" => {
+                // a single whitespace character separates the header from the samples
+                let data = bytes
+                    .get(position + 1..)
+                    .ok_or_else(|| invalid_data("missing PGM samples"))?;
+                let sample_size = if max_value < 256 { 1 } else { 2 };
+                if count
+                    .checked_mul(sample_size)
+                    .is_none_or(|size| data.len() < size)
+                {
+                    return Err(invalid_data("missing PGM samples"));
+                }
+
+                if sample_size == 1 {
+                    data[..count].iter().map(|h| *h as u16).collect()
+                } else {
+                    data[..count * 2]
+                        .chunks(2)
+                        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                        .collect()
+                }
+            }
+            _ => return Err(invalid_data("not a PGM image")),
+        };
+
+        Ok(Heightmap::new(width, depth, max_value as u16, heights))
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn get_max_value(&self) -> u16 {
+        self.max_value
+    }
+
+    pub fn get(&self, x: usize, z: usize) -> u16 {
+        self.heights[x + z * self.width]
+    }
+
+    /// Averages every sample with its neighbours up to `radius` samples away, to soften the
+    /// terraces left by 8 bit heightmaps. Samples past the edges repeat the edge samples.
+    pub fn smooth(&self, radius: usize) -> Heightmap {
+        let radius = radius as i64;
+        let clamp = |v: i64, size: usize| v.clamp(0, size as i64 - 1) as usize;
+        let mut heights = Vec::with_capacity(self.heights.len());
+
+        for z in 0..self.depth as i64 {
+            for x in 0..self.width as i64 {
+                let mut sum = 0u64;
+                for dz in -radius..=radius {
+                    for dx in -radius..=radius {
+                        sum +=
+                            self.get(clamp(x + dx, self.width), clamp(z + dz, self.depth)) as u64;
+                    }
+                }
+
+                let count = ((radius * 2 + 1) * (radius * 2 + 1)) as u64;
+                heights.push(((sum + count / 2) / count) as u16);
+            }
+        }
+
+        Heightmap::new(self.width, self.depth, self.max_value, heights)
+    }
+
+    /// Builds a volume with one column of voxels per sample.
+    ///
+    /// The highest possible sample becomes `vertical_scale` voxels tall. Each column is filled
+    /// from the top down with the `layers` in order, and the last layer continues to the bottom of
+    /// the volume, so `[grass 1, dirt 3, stone 1]` puts grass over dirt over stone. Fails if the
+    /// volume would need more than `i32::MAX` voxels.
+    pub fn to_raw_volume<T>(
+        &self,
+        vertical_scale: f64,
+        layers: &[Layer<T>],
+    ) -> io::Result<RawVolume<T>>
+    where
+        T: Voxel,
+    {
+        let height = (vertical_scale.ceil() as i64).max(1);
+        (self.width as i64)
+            .checked_mul(self.depth as i64)
+            .and_then(|area| area.checked_mul(height))
+            .filter(|size| *size <= i32::MAX as i64)
+            .ok_or_else(|| invalid_data("heightmap volume is too large"))?;
+
+        let height = height as i32;
+        let region = Region::new(
+            Vec3::zero(),
+            Vec3::new(self.width as i32 - 1, height - 1, self.depth as i32 - 1),
+        );
+        let mut volume = RawVolume::new(region.clone());
+        let scale = vertical_scale / self.max_value as f64;
+
+        generate_heightmap(
+            &mut volume,
+            &region,
+            |x, z| (self.get(x as usize, z as usize) as f64 * scale).round(),
+            |_, depth| {
+                let mut remaining = depth as i32 - 1;
+                for layer in layers {
+                    if remaining < layer.thickness {
+                        return layer.material;
+                    }
+                    remaining -= layer.thickness;
+                }
+
+                layers.last().map(|l| l.material).unwrap_or_default()
+            },
+        );
+
+        Ok(volume)
+    }
+}
@@ -1,11 +1,33 @@
 pub use vek;
+pub mod octree_volume;
+pub mod palette;
 pub mod raw_volume;
 pub mod raw_volume_sampler;
 pub mod region;
+pub mod resampling;
 pub mod sampler;
+pub mod sdf;
+pub mod sdf_volume;
+pub mod sparse_volume;
 pub mod volume;
+pub mod volume_sampler;
 pub mod voxel;
 
+pub mod collision;
+pub mod connectivity;
+pub mod csg;
 pub mod cubic_surface_extractor;
+pub mod decimation;
+pub mod dirty_tracker;
+pub mod generator;
+pub mod heightmap;
+pub mod lighting;
+pub mod lod;
 pub mod mesh;
+pub mod morphology;
+pub mod pathfinding;
+pub mod surface_nets;
+pub mod texture_atlas;
+pub mod transform;
 pub mod vertex;
+pub mod voxelizer;
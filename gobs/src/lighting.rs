@@ -0,0 +1,225 @@
+use crate::connectivity::Connectivity;
+use crate::raw_volume::RawVolume;
+use crate::region::Region;
+use crate::volume::Volume;
+use crate::voxel::Voxel;
+use std::collections::VecDeque;
+use vek::vec3::Vec3;
+
+/// The brightest light level, given to voxels open to the sky and the brightest light sources.
+pub const MAX_LIGHT: u8 = 15;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Channel {
+    Sun,
+    Block,
+}
+
+/// The light levels of the voxels in a region, kept alongside the volume they were calculated
+/// from.
+///
+/// Light travels through empty voxels, losing one level per voxel. Sunlight comes down from the
+/// top of the region and keeps its full level while travelling straight down. Block light comes
+/// from non-empty voxels with an emission level, which are lit to that level themselves. Every
+/// other non-empty voxel is unlit.
+pub struct LightVolume {
+    // sunlight in the high four bits and block light in the low four
+    levels: RawVolume<u8>,
+}
+
+impl LightVolume {
+    pub fn new(region: Region) -> Self {
+        LightVolume {
+            levels: RawVolume::new(region),
+        }
+    }
+
+    pub fn get_region(&self) -> &Region {
+        self.levels.get_region()
+    }
+
+    pub fn get_sunlight(&self, x: i32, y: i32, z: i32) -> u8 {
+        self.levels.get_voxel_at(x, y, z) >> 4
+    }
+
+    pub fn get_block_light(&self, x: i32, y: i32, z: i32) -> u8 {
+        self.levels.get_voxel_at(x, y, z) & 0xf
+    }
+
+    /// The brighter of the sunlight and block light at a position.
+    pub fn get_light(&self, x: i32, y: i32, z: i32) -> u8 {
+        self.get_sunlight(x, y, z)
+            .max(self.get_block_light(x, y, z))
+    }
+
+    fn get(&self, channel: Channel, p: Vec3<i32>) -> u8 {
+        match channel {
+            Channel::Sun => self.get_sunlight(p.x, p.y, p.z),
+            Channel::Block => self.get_block_light(p.x, p.y, p.z),
+        }
+    }
+
+    fn set(&mut self, channel: Channel, p: Vec3<i32>, level: u8) {
+        let levels = self.levels.get_voxel_at(p.x, p.y, p.z);
+        let levels = match channel {
+            Channel::Sun => (levels & 0xf) | (level << 4),
+            Channel::Block => (levels & 0xf0) | level,
+        };
+        self.levels.set_voxel_at(p.x, p.y, p.z, levels).unwrap();
+    }
+
+    /// Calculates the light levels of the whole region from scratch.
+    ///
+    /// `emission` gives the light level each voxel gives off, which is clamped to `MAX_LIGHT`.
+    pub fn calculate<T, F>(&mut self, volume: &dyn Volume<T>, emission: F)
+    where
+        T: Voxel,
+        F: Fn(T) -> u8,
+    {
+        for (_, levels) in self.levels.iter_mut() {
+            *levels = 0;
+        }
+
+        let region = self.get_region().clone();
+        let mut queue = VecDeque::new();
+
+        for x in region.lower_x..=region.upper_x {
+            for z in region.lower_z..=region.upper_z {
+                for y in (region.lower_y..=region.upper_y).rev() {
+                    if !volume.get_voxel_at(x, y, z).is_empty() {
+                        break;
+                    }
+
+                    let p = Vec3::new(x, y, z);
+                    self.set(Channel::Sun, p, MAX_LIGHT);
+                    queue.push_back(p);
+                }
+            }
+        }
+        self.spread(volume, Channel::Sun, &mut queue);
+
+        for p in region.iter() {
+            let level = emission(volume.get_voxel_at(p.x, p.y, p.z)).min(MAX_LIGHT);
+            if level > 0 {
+                self.set(Channel::Block, p, level);
+                queue.push_back(p);
+            }
+        }
+        self.spread(volume, Channel::Block, &mut queue);
+    }
+
+    /// Updates the light levels after the voxel at `position` has been changed in `volume`.
+    ///
+    /// Only the light which came from or through the changed voxel is recalculated, so this is
+    /// much cheaper than `calculate` for single edits.
+    pub fn update<T, F>(&mut self, volume: &dyn Volume<T>, position: Vec3<i32>, emission: F)
+    where
+        T: Voxel,
+        F: Fn(T) -> u8,
+    {
+        let region = self.get_region().clone();
+        let offsets = Connectivity::Six.offsets();
+        if !region.contains_point(position.x, position.y, position.z) {
+            return;
+        }
+
+        let voxel = volume.get_voxel_at(position.x, position.y, position.z);
+        let emission = |p: Vec3<i32>| emission(volume.get_voxel_at(p.x, p.y, p.z)).min(MAX_LIGHT);
+
+        for channel in [Channel::Sun, Channel::Block] {
+            let mut removed = VecDeque::new();
+            let mut queue = VecDeque::new();
+
+            removed.push_back((position, self.get(channel, position)));
+            self.set(channel, position, 0);
+
+            // darken everything which was lit through the changed voxel, collecting the lit
+            // voxels around the darkened area to spread light back in from
+            while let Some((p, level)) = removed.pop_front() {
+                for offset in offsets.iter().copied() {
+                    let next = p + offset;
+                    if !region.contains_point(next.x, next.y, next.z) {
+                        continue;
+                    }
+
+                    let next_level = self.get(channel, next);
+                    if next_level == 0 {
+                        continue;
+                    }
+
+                    let lit_from_p = next_level < level
+                        || (channel == Channel::Sun
+                            && offset.y == -1
+                            && level == MAX_LIGHT
+                            && next_level == MAX_LIGHT);
+                    if lit_from_p && (channel == Channel::Sun || emission(next) == 0) {
+                        self.set(channel, next, 0);
+                        removed.push_back((next, next_level));
+                    } else {
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            match channel {
+                Channel::Sun => {
+                    let open_to_sky = position.y == region.upper_y
+                        || self.get(Channel::Sun, position + Vec3::unit_y()) == MAX_LIGHT;
+                    if voxel.is_empty() && open_to_sky {
+                        self.set(Channel::Sun, position, MAX_LIGHT);
+                        queue.push_back(position);
+                    }
+                }
+                Channel::Block => {
+                    let level = emission(position);
+                    if level > 0 {
+                        self.set(Channel::Block, position, level);
+                        queue.push_back(position);
+                    }
+                }
+            }
+
+            self.spread(volume, channel, &mut queue);
+        }
+    }
+
+    /// Spreads light out from the queued voxels through empty voxels.
+    fn spread<T>(
+        &mut self,
+        volume: &dyn Volume<T>,
+        channel: Channel,
+        queue: &mut VecDeque<Vec3<i32>>,
+    ) where
+        T: Voxel,
+    {
+        let region = self.get_region().clone();
+        let offsets = Connectivity::Six.offsets();
+
+        while let Some(p) = queue.pop_front() {
+            let level = self.get(channel, p);
+            if level <= 1 {
+                continue;
+            }
+
+            for offset in offsets.iter().copied() {
+                let next = p + offset;
+                if !region.contains_point(next.x, next.y, next.z)
+                    || !volume.get_voxel_at(next.x, next.y, next.z).is_empty()
+                {
+                    continue;
+                }
+
+                let next_level = if channel == Channel::Sun && offset.y == -1 && level == MAX_LIGHT
+                {
+                    MAX_LIGHT
+                } else {
+                    level - 1
+                };
+                if next_level > self.get(channel, next) {
+                    self.set(channel, next, next_level);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use crate::cubic_surface_extractor::{extract_cubic_mesh, CubicVertex};
+use crate::mesh::{FaceArity, Mesh};
+use crate::raw_volume::RawVolume;
+use crate::raw_volume_sampler::RawVolumeSampler;
+use crate::region::Region;
+use crate::volume::Volume;
+use crate::voxel::{Density, Voxel};
+use vek::vec3::Vec3;
+
+/// Picks the value that occurs most often, preferring non-empty values on a tie.
+pub fn most_common<T>(values: &[T]) -> T
+where
+    T: Voxel,
+{
+    let mut result: T = Default::default();
+    let mut result_count = 0;
+
+    for (i, value) in values.iter().enumerate() {
+        if values[..i].contains(value) {
+            continue;
+        }
+
+        let count = values[i..].iter().filter(|v| *v == value).count();
+        if count > result_count || (count == result_count && result.is_empty()) {
+            result = *value;
+            result_count = count;
+        }
+    }
+
+    result
+}
+
+/// Averages the values, for density volumes.
+pub fn average<T>(values: &[T]) -> T
+where
+    T: Density,
+{
+    if values.is_empty() {
+        return Default::default();
+    }
+
+    let sum: f64 = values.iter().map(|v| v.to_f64()).sum();
+
+    T::from_f64(sum / values.len() as f64)
+}
+
+/// Shrinks `volume` by `factor` along each axis.
+///
+/// Every voxel of the result covers a block of `factor`³ source voxels, which `reduce` combines
+/// into one value. Blocks on the edges of the volume only pass the voxels inside the volume.
+pub fn downsample<T, F>(volume: &RawVolume<T>, factor: i32, reduce: F) -> RawVolume<T>
+where
+    T: Voxel,
+    F: Fn(&[T]) -> T,
+{
+    assert!(factor > 0);
+
+    let source = volume.get_region();
+    let region = Region::new(
+        source.get_lower_corner().map(|c| c.div_euclid(factor)),
+        source.get_upper_corner().map(|c| c.div_euclid(factor)),
+    );
+    let mut result = RawVolume::new(region.clone());
+    result.set_border_value(volume.get_border_value());
+
+    let mut block = Vec::with_capacity((factor * factor * factor) as usize);
+    for z in region.lower_z..=region.upper_z {
+        for y in region.lower_y..=region.upper_y {
+            for x in region.lower_x..=region.upper_x {
+                block.clear();
+
+                for sz in z * factor..(z + 1) * factor {
+                    for sy in y * factor..(y + 1) * factor {
+                        for sx in x * factor..(x + 1) * factor {
+                            if source.contains_point(sx, sy, sz) {
+                                block.push(volume.get_voxel_at(sx, sy, sz));
+                            }
+                        }
+                    }
+                }
+
+                result.set_voxel_at(x, y, z, reduce(&block)).unwrap();
+            }
+        }
+    }
+
+    result
+}
+
+/// Builds `levels` progressively smaller copies of `volume`, downsampled by 2, 4, 8 and so on.
+pub fn downsample_levels<T, F>(volume: &RawVolume<T>, levels: usize, reduce: F) -> Vec<RawVolume<T>>
+where
+    T: Voxel,
+    F: Fn(&[T]) -> T,
+{
+    (1..=levels)
+        .map(|level| downsample(volume, 1 << level, &reduce))
+        .collect()
+}
+
+/// The mesh for one chunk of a volume, extracted at a given level of detail.
+///
+/// Vertex positions and the mesh offset are measured in voxels of that level. Multiply them by
+/// `1 << level` to get positions in the full resolution volume.
+pub struct LodChunkMesh<T>
+where
+    T: Voxel,
+{
+    pub chunk: Vec3<i32>,
+    pub level: usize,
+    pub mesh: Mesh<CubicVertex<T>>,
+}
+
+const NEIGHBOURS: [(i32, i32, i32); 6] = [
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+fn copy_region<T>(source: &RawVolume<T>, destination: &mut RawVolume<T>, region: &Region)
+where
+    T: Voxel,
+{
+    for z in region.lower_z..=region.upper_z {
+        for y in region.lower_y..=region.upper_y {
+            for x in region.lower_x..=region.upper_x {
+                destination
+                    .set_voxel_at(x, y, z, source.get_voxel_at(x, y, z))
+                    .unwrap();
+            }
+        }
+    }
+}
+
+fn get_level_region(region: &Region, level: usize) -> Region {
+    Region::new(
+        region.get_lower_corner().map(|c| c >> level),
+        region.get_upper_corner().map(|c| c >> level),
+    )
+}
+
+/// Returns the one voxel thick slab adjacent to `region` in the given direction.
+fn get_shell(region: &Region, dx: i32, dy: i32, dz: i32) -> Region {
+    let mut shell = region.clone();
+    match (dx, dy, dz) {
+        (-1, _, _) => {
+            shell.lower_x -= 1;
+            shell.upper_x = shell.lower_x;
+        }
+        (1, _, _) => {
+            shell.upper_x += 1;
+            shell.lower_x = shell.upper_x;
+        }
+        (_, -1, _) => {
+            shell.lower_y -= 1;
+            shell.upper_y = shell.lower_y;
+        }
+        (_, 1, _) => {
+            shell.upper_y += 1;
+            shell.lower_y = shell.upper_y;
+        }
+        (_, _, -1) => {
+            shell.lower_z -= 1;
+            shell.upper_z = shell.lower_z;
+        }
+        _ => {
+            shell.upper_z += 1;
+            shell.lower_z = shell.upper_z;
+        }
+    }
+
+    shell
+}
+
+/// Splits the volume into chunks and extracts each one at the level chosen by `choose_level`.
+///
+/// `levels[0]` is the full resolution volume and `levels[n]` has been downsampled by `2ⁿ`, for
+/// example by `downsample_levels`. `chunk_size` is measured in full resolution voxels and must be
+/// a multiple of the largest downsampling factor.
+///
+/// Where a chunk borders a chunk of a different level the two surfaces no longer line up, so the
+/// chunk is closed off with transition faces along that border to hide the crack.
+pub fn extract_lod_cubic_meshes<T, F>(
+    levels: &[&RawVolume<T>],
+    chunk_size: i32,
+    choose_level: F,
+    face_arity: Option<FaceArity>,
+) -> Option<Vec<LodChunkMesh<T>>>
+where
+    T: Voxel,
+    F: Fn(Vec3<i32>) -> usize,
+{
+    let full_region = levels.first()?.get_region();
+    assert!(chunk_size > 0 && chunk_size % (1 << (levels.len() - 1)) == 0);
+
+    let lower_chunk = full_region
+        .get_lower_corner()
+        .map(|c| c.div_euclid(chunk_size));
+    let upper_chunk = full_region
+        .get_upper_corner()
+        .map(|c| c.div_euclid(chunk_size));
+
+    let chunk_region = |chunk: Vec3<i32>| {
+        let lower = chunk * chunk_size;
+        Region::new(lower, lower + (chunk_size - 1)).intersect(full_region)
+    };
+
+    let mut result = vec![];
+    for cz in lower_chunk.z..=upper_chunk.z {
+        for cy in lower_chunk.y..=upper_chunk.y {
+            for cx in lower_chunk.x..=upper_chunk.x {
+                let chunk = Vec3::new(cx, cy, cz);
+                let level = choose_level(chunk).min(levels.len() - 1);
+                let volume = levels[level];
+                let region = get_level_region(&chunk_region(chunk)?, level);
+
+                let padded =
+                    Region::new(region.get_lower_corner() - 1, region.get_upper_corner() + 1);
+
+                let mut chunk_volume = RawVolume::new(padded);
+                copy_region(volume, &mut chunk_volume, &region);
+
+                let mut extract_region = region.clone();
+                for (dx, dy, dz) in NEIGHBOURS.iter() {
+                    let neighbour = chunk + Vec3::new(*dx, *dy, *dz);
+                    let same_level = chunk_region(neighbour).is_none()
+                        || choose_level(neighbour).min(levels.len() - 1) == level;
+
+                    if same_level {
+                        let shell = get_shell(&region, *dx, *dy, *dz);
+                        copy_region(volume, &mut chunk_volume, &shell);
+                    } else {
+                        // leave the shell empty and extend the extraction over it, so the faces
+                        // on the positive side of the chunk are generated here as well
+                        match (dx, dy, dz) {
+                            (1, _, _) => extract_region.upper_x += 1,
+                            (_, 1, _) => extract_region.upper_y += 1,
+                            (_, _, 1) => extract_region.upper_z += 1,
+                            _ => {}
+                        }
+                    }
+                }
+
+                let mesh = extract_cubic_mesh(
+                    &mut RawVolumeSampler::new(&chunk_volume),
+                    &extract_region,
+                    face_arity,
+                    Some(true),
+                )?;
+
+                result.push(LodChunkMesh { chunk, level, mesh });
+            }
+        }
+    }
+
+    Some(result)
+}
@@ -1,12 +1,14 @@
 use vek::vec3::Vec3;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FaceArity {
     Three,
     Four,
 }
 
 /// A polygon mesh.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Mesh<T> {
     pub(crate) indices: Vec<i32>,
     pub(crate) vertices: Vec<T>,
@@ -80,7 +82,7 @@ impl<T> Mesh<T> {
 
     #[inline]
     pub fn face_arity(&self) -> FaceArity {
-        self.face_arity.clone()
+        self.face_arity
     }
 
     pub fn remove_unused_vertices(&mut self) {
@@ -98,7 +100,7 @@ impl<T> Mesh<T> {
                     self.vertices.swap(used_count, i);
                 }
                 new_pos[i] = used_count;
-                used_count = used_count + 1
+                used_count += 1
             }
         }
 
@@ -108,3 +110,43 @@ impl<T> Mesh<T> {
         }
     }
 }
+
+/// The serialized form of a `Mesh`, checked before it is turned back into one.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct EncodedMesh<T> {
+    indices: Vec<i32>,
+    vertices: Vec<T>,
+    offset: Vec3<i32>,
+    face_arity: FaceArity,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Mesh<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let encoded = EncodedMesh::<T>::deserialize(deserializer)?;
+        let vertex_count = encoded.vertices.len();
+        if encoded
+            .indices
+            .iter()
+            .any(|i| *i < 0 || *i as usize >= vertex_count)
+        {
+            return Err(D::Error::custom("mesh index out of range"));
+        }
+
+        Ok(Mesh {
+            indices: encoded.indices,
+            vertices: encoded.vertices,
+            offset: encoded.offset,
+            face_arity: encoded.face_arity,
+        })
+    }
+}
@@ -0,0 +1,221 @@
+use crate::connectivity::Connectivity;
+use crate::raw_volume::RawVolume;
+use crate::region::Region;
+use crate::volume::Volume;
+use crate::voxel::Voxel;
+use std::collections::VecDeque;
+use vek::vec3::Vec3;
+
+/// The neighbourhood looked at around each voxel by `dilate` and `erode`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StructuringElement {
+    /// Every voxel within this many voxels along each axis.
+    Cube(i32),
+    /// Every voxel whose centre is within this distance.
+    Sphere(i32),
+    /// Every voxel within this many steps along the axes, an octahedron.
+    Diamond(i32),
+    /// These offsets from the voxel.
+    Custom(Vec<Vec3<i32>>),
+}
+
+impl StructuringElement {
+    /// The offsets covered by the element, nearest first, leaving out the voxel itself.
+    pub fn offsets(&self) -> Vec<Vec3<i32>> {
+        let within = |radius: i32, f: &dyn Fn(Vec3<i32>) -> bool| -> Vec<Vec3<i32>> {
+            Region::new(Vec3::broadcast(-radius), Vec3::broadcast(radius))
+                .iter()
+                .filter(|offset| f(*offset))
+                .collect()
+        };
+
+        let mut offsets = match self {
+            StructuringElement::Cube(radius) => within(*radius, &|_| true),
+            StructuringElement::Sphere(radius) => {
+                within(*radius, &|o| o.magnitude_squared() <= radius * radius)
+            }
+            StructuringElement::Diamond(radius) => {
+                within(*radius, &|o| o.map(i32::abs).sum() <= *radius)
+            }
+            StructuringElement::Custom(offsets) => offsets.clone(),
+        };
+
+        offsets.retain(|offset| *offset != Vec3::zero());
+        offsets.sort_by_key(|offset| offset.magnitude_squared());
+
+        offsets
+    }
+
+    /// How far the element reaches from the voxel along any axis.
+    pub fn radius(&self) -> i32 {
+        self.offsets()
+            .iter()
+            .map(|offset| offset.map(i32::abs).reduce_max())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Grows the non-empty voxels of `region` by the structuring element.
+///
+/// Each non-empty voxel spreads to the positions of the element around it, and each empty voxel
+/// reached takes the material of the nearest voxel reaching it. Voxels outside `region` are read
+/// from `volume` but never written.
+pub fn dilate<T>(
+    volume: &dyn Volume<T>,
+    region: &Region,
+    element: &StructuringElement,
+) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    let offsets = element.offsets();
+    let mut result = RawVolume::new(region.clone());
+
+    for (p, voxel) in result.iter_mut() {
+        *voxel = volume.get_voxel_at(p.x, p.y, p.z);
+        if !voxel.is_empty() {
+            continue;
+        }
+
+        // the element is reflected, so a voxel spreads to wherever it is under the element, which
+        // makes dilation undo erosion for asymmetric elements too
+        if let Some(material) = offsets
+            .iter()
+            .map(|o| volume.get_voxel_at(p.x - o.x, p.y - o.y, p.z - o.z))
+            .find(|v| !v.is_empty())
+        {
+            *voxel = material;
+        }
+    }
+
+    result
+}
+
+/// Shrinks the non-empty voxels of `region` by the structuring element, emptying every voxel with
+/// an empty voxel under the element.
+pub fn erode<T>(
+    volume: &dyn Volume<T>,
+    region: &Region,
+    element: &StructuringElement,
+) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    let offsets = element.offsets();
+    let mut result = RawVolume::new(region.clone());
+
+    for (p, voxel) in result.iter_mut() {
+        let original = volume.get_voxel_at(p.x, p.y, p.z);
+        let exposed = offsets.iter().any(|o| {
+            volume
+                .get_voxel_at(p.x + o.x, p.y + o.y, p.z + o.z)
+                .is_empty()
+        });
+        if !exposed {
+            *voxel = original;
+        }
+    }
+
+    result
+}
+
+/// Erodes then dilates, removing specks and thin spurs smaller than the element.
+pub fn open<T>(
+    volume: &dyn Volume<T>,
+    region: &Region,
+    element: &StructuringElement,
+) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    // the first pass covers a larger region so the second sees the true neighbours at the edges
+    let eroded = erode(volume, &region.grow(element.radius()), element);
+
+    dilate(&eroded, region, element)
+}
+
+/// Dilates then erodes, filling gaps and small holes smaller than the element.
+pub fn close<T>(
+    volume: &dyn Volume<T>,
+    region: &Region,
+    element: &StructuringElement,
+) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    let dilated = dilate(volume, &region.grow(element.radius()), element);
+
+    erode(&dilated, region, element)
+}
+
+/// Keeps only the non-empty voxels with an empty neighbour, emptying the inside of solid shapes.
+pub fn hollow<T>(
+    volume: &dyn Volume<T>,
+    region: &Region,
+    connectivity: Connectivity,
+) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    let offsets = connectivity.offsets();
+    let mut result = RawVolume::new(region.clone());
+
+    for (p, voxel) in result.iter_mut() {
+        let original = volume.get_voxel_at(p.x, p.y, p.z);
+        let surface = offsets.iter().any(|o| {
+            volume
+                .get_voxel_at(p.x + o.x, p.y + o.y, p.z + o.z)
+                .is_empty()
+        });
+        if surface {
+            *voxel = original;
+        }
+    }
+
+    result
+}
+
+/// Fills every empty space in `region` which is completely enclosed by non-empty voxels with
+/// `material`.
+///
+/// Empty voxels are enclosed if they can not be reached from the edge of the region by stepping
+/// between empty voxels sharing a face.
+pub fn fill_interior<T>(volume: &dyn Volume<T>, region: &Region, material: T) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    let mut result = RawVolume::new(region.clone());
+    let mut outside = RawVolume::<u8>::new(region.clone());
+    let mut queue = VecDeque::new();
+
+    for (p, voxel) in result.iter_mut() {
+        *voxel = volume.get_voxel_at(p.x, p.y, p.z);
+        if voxel.is_empty() && !region.contains_point_excluding_boundary(p.x, p.y, p.z, 1) {
+            outside.set_voxel_at(p.x, p.y, p.z, 1).unwrap();
+            queue.push_back(p);
+        }
+    }
+
+    let offsets = Connectivity::Six.offsets();
+    while let Some(p) = queue.pop_front() {
+        for offset in offsets.iter() {
+            let next = p + *offset;
+            if region.contains_point(next.x, next.y, next.z)
+                && outside.get_voxel_at(next.x, next.y, next.z) == 0
+                && result.get_voxel_at(next.x, next.y, next.z).is_empty()
+            {
+                outside.set_voxel_at(next.x, next.y, next.z, 1).unwrap();
+                queue.push_back(next);
+            }
+        }
+    }
+
+    for (p, voxel) in result.iter_mut() {
+        if voxel.is_empty() && outside.get_voxel_at(p.x, p.y, p.z) == 0 {
+            *voxel = material;
+        }
+    }
+
+    result
+}
@@ -0,0 +1,492 @@
+use crate::cubic_surface_extractor::extract_cubic_mesh;
+use crate::dirty_tracker::ChunkMesh;
+use crate::mesh::FaceArity;
+use crate::raw_volume::RawVolume;
+use crate::raw_volume_sampler::RawVolumeSampler;
+use crate::region::Region;
+use crate::volume::{PositionError, Volume};
+use crate::voxel::Voxel;
+use vek::vec3::Vec3;
+
+enum Node<T>
+where
+    T: Voxel,
+{
+    Leaf(T),
+    Branch(Box<[Node<T>; 8]>),
+}
+
+impl<T> Node<T>
+where
+    T: Voxel,
+{
+    /// Returns the value shared by all children, if they are all leaves holding the same value.
+    fn get_uniform_children(children: &[Node<T>; 8]) -> Option<T> {
+        match children[0] {
+            Node::Leaf(first) => {
+                if children.iter().all(|child| match child {
+                    Node::Leaf(value) => *value == first,
+                    _ => false,
+                }) {
+                    Some(first)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn count_nodes(&self) -> usize {
+        match self {
+            Node::Leaf(_) => 1,
+            Node::Branch(children) => 1 + children.iter().map(Node::count_nodes).sum::<usize>(),
+        }
+    }
+}
+
+fn get_child_index(lower: Vec3<i32>, half: i32, x: i32, y: i32, z: i32) -> usize {
+    let mut index = 0;
+    if x >= lower.x + half {
+        index |= 1;
+    }
+    if y >= lower.y + half {
+        index |= 2;
+    }
+    if z >= lower.z + half {
+        index |= 4;
+    }
+
+    index
+}
+
+fn get_child_lower(lower: Vec3<i32>, half: i32, index: usize) -> Vec3<i32> {
+    Vec3::new(
+        lower.x + if index & 1 != 0 { half } else { 0 },
+        lower.y + if index & 2 != 0 { half } else { 0 },
+        lower.z + if index & 4 != 0 { half } else { 0 },
+    )
+}
+
+fn get_node_region(lower: Vec3<i32>, size: i32) -> Region {
+    Region::new(lower, lower + (size - 1))
+}
+
+/// The result of a successful `OctreeVolume::raycast`.
+#[derive(Clone, Debug)]
+pub struct RaycastHit<T>
+where
+    T: Voxel,
+{
+    pub position: Vec3<i32>,
+    pub distance: f32,
+    pub voxel: T,
+}
+
+/// A volume stored as a sparse voxel octree.
+///
+/// Any part of the tree where every voxel has the same value is collapsed into a single leaf, so
+/// large empty or solid areas take up almost no memory. The tree is a cube whose side is the
+/// smallest power of two covering `valid_region`, anchored at its lower corner, so regions can be
+/// at most 2^30 voxels across.
+pub struct OctreeVolume<T>
+where
+    T: Voxel,
+{
+    root: Node<T>,
+    size: i32,
+    border_value: T,
+    valid_region: Region,
+}
+
+impl<T> OctreeVolume<T>
+where
+    T: Voxel,
+{
+    pub fn new(region: Region) -> Self {
+        let extent = region
+            .get_width()
+            .max(region.get_height())
+            .max(region.get_depth());
+        let size = (extent.max(1) as u32).next_power_of_two();
+        assert!(
+            size <= 1 << 30,
+            "octree regions can be at most 2^30 voxels across"
+        );
+        let size = size as i32;
+        assert!(
+            region
+                .get_lower_corner()
+                .iter()
+                .all(|c| c.checked_add(size - 1).is_some()),
+            "octree cube must fit within i32 coordinates"
+        );
+
+        OctreeVolume {
+            root: Node::Leaf(Default::default()),
+            size,
+            border_value: Default::default(),
+            valid_region: region,
+        }
+    }
+
+    pub fn from_raw_volume(volume: &RawVolume<T>) -> Self {
+        let mut result = OctreeVolume::new(volume.get_region().clone());
+        result.border_value = volume.get_border_value();
+        result.root = result.build(volume, result.valid_region.get_lower_corner(), result.size);
+
+        result
+    }
+
+    fn build(&self, volume: &RawVolume<T>, lower: Vec3<i32>, size: i32) -> Node<T> {
+        // the parts of the cube outside the region are never read, so they stay as one leaf
+        if get_node_region(lower, size)
+            .intersect(&self.valid_region)
+            .is_none()
+        {
+            return Node::Leaf(Default::default());
+        }
+
+        if size == 1 {
+            return Node::Leaf(volume.get_voxel_at(lower.x, lower.y, lower.z));
+        }
+
+        let half = size / 2;
+        let children = Box::new(
+            [0, 1, 2, 3, 4, 5, 6, 7]
+                .map(|index| self.build(volume, get_child_lower(lower, half, index), half)),
+        );
+
+        match Node::get_uniform_children(&children) {
+            Some(value) => Node::Leaf(value),
+            None => Node::Branch(children),
+        }
+    }
+
+    pub fn to_raw_volume(&self) -> RawVolume<T> {
+        self.to_raw_volume_region(&self.valid_region)
+    }
+
+    /// Copies `region` into a new `RawVolume`. Voxels outside of this volume get the border value.
+    pub fn to_raw_volume_region(&self, region: &Region) -> RawVolume<T> {
+        let mut result = RawVolume::new(region.clone());
+        result.set_border_value(self.border_value);
+
+        for z in region.lower_z..=region.upper_z {
+            for y in region.lower_y..=region.upper_y {
+                for x in region.lower_x..=region.upper_x {
+                    result
+                        .set_voxel_at(x, y, z, self.get_voxel_at(x, y, z))
+                        .unwrap();
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn set_border_value(&mut self, value: T) {
+        self.border_value = value;
+    }
+
+    pub fn get_node_count(&self) -> usize {
+        self.root.count_nodes()
+    }
+
+    /// Finds the leaf containing the given position, returning its lower corner, size and value.
+    fn find_leaf(&self, x: i32, y: i32, z: i32) -> (Vec3<i32>, i32, T) {
+        let mut node = &self.root;
+        let mut lower = self.valid_region.get_lower_corner();
+        let mut size = self.size;
+
+        loop {
+            match node {
+                Node::Leaf(value) => return (lower, size, *value),
+                Node::Branch(children) => {
+                    size /= 2;
+                    let index = get_child_index(lower, size, x, y, z);
+                    lower = get_child_lower(lower, size, index);
+                    node = &children[index];
+                }
+            }
+        }
+    }
+
+    fn set_in_node(node: &mut Node<T>, lower: Vec3<i32>, size: i32, position: Vec3<i32>, voxel: T) {
+        if size == 1 {
+            *node = Node::Leaf(voxel);
+            return;
+        }
+
+        if let Node::Leaf(value) = *node {
+            if value == voxel {
+                return;
+            }
+            *node = Node::Branch(Box::new([0; 8].map(|_| Node::Leaf(value))));
+        }
+
+        if let Node::Branch(children) = node {
+            let half = size / 2;
+            let index = get_child_index(lower, half, position.x, position.y, position.z);
+            OctreeVolume::set_in_node(
+                &mut children[index],
+                get_child_lower(lower, half, index),
+                half,
+                position,
+                voxel,
+            );
+
+            if let Some(value) = Node::get_uniform_children(children) {
+                *node = Node::Leaf(value);
+            }
+        }
+    }
+
+    /// Returns the value of every voxel in `region` if they are all the same.
+    ///
+    /// Voxels outside of the volume count as the border value. Collapsed parts of the tree are
+    /// checked without visiting their voxels.
+    pub fn get_uniform_value(&self, region: &Region) -> Option<T> {
+        let mut result = None;
+
+        match region.intersect(&self.valid_region) {
+            Some(inside) => {
+                if inside.get_volume() != region.get_volume() {
+                    result = Some(self.border_value);
+                }
+
+                if !self.visit_uniform(
+                    &self.root,
+                    self.valid_region.get_lower_corner(),
+                    self.size,
+                    &inside,
+                    &mut result,
+                ) {
+                    return None;
+                }
+            }
+            None => result = Some(self.border_value),
+        }
+
+        result
+    }
+
+    fn visit_uniform(
+        &self,
+        node: &Node<T>,
+        lower: Vec3<i32>,
+        size: i32,
+        query: &Region,
+        result: &mut Option<T>,
+    ) -> bool {
+        if get_node_region(lower, size).intersect(query).is_none() {
+            return true;
+        }
+
+        match node {
+            Node::Leaf(value) => match result {
+                Some(existing) => *existing == *value,
+                None => {
+                    *result = Some(*value);
+                    true
+                }
+            },
+            Node::Branch(children) => {
+                let half = size / 2;
+                children.iter().enumerate().all(|(index, child)| {
+                    self.visit_uniform(
+                        child,
+                        get_child_lower(lower, half, index),
+                        half,
+                        query,
+                        result,
+                    )
+                })
+            }
+        }
+    }
+
+    /// Finds the first non-empty voxel along a ray, up to `max_distance` from `origin`.
+    ///
+    /// Empty leaves of the tree are crossed in a single step, so the cost depends on the number
+    /// of leaves the ray passes through rather than the number of voxels.
+    pub fn raycast(
+        &self,
+        origin: Vec3<f32>,
+        direction: Vec3<f32>,
+        max_distance: f32,
+    ) -> Option<RaycastHit<T>> {
+        let lower = self.valid_region.get_lower_corner().map(|c| c as f32);
+        let upper = self.valid_region.get_upper_corner().map(|c| (c + 1) as f32);
+
+        // clip the ray against the bounds of the volume
+        let mut t_enter = 0.0f32;
+        let mut t_exit = max_distance;
+        for axis in 0..3 {
+            if direction[axis] == 0.0 {
+                if origin[axis] < lower[axis] || origin[axis] >= upper[axis] {
+                    return None;
+                }
+            } else {
+                let t0 = (lower[axis] - origin[axis]) / direction[axis];
+                let t1 = (upper[axis] - origin[axis]) / direction[axis];
+                t_enter = t_enter.max(t0.min(t1));
+                t_exit = t_exit.min(t0.max(t1));
+            }
+        }
+        if t_enter > t_exit {
+            return None;
+        }
+
+        let region_lower = self.valid_region.get_lower_corner();
+        let region_upper = self.valid_region.get_upper_corner();
+        let mut t = t_enter;
+        let mut cell = Vec3::min(
+            Vec3::max(
+                (origin + direction * t).map(|c| c.floor() as i32),
+                region_lower,
+            ),
+            region_upper,
+        );
+
+        while self.valid_region.contains_point(cell.x, cell.y, cell.z) && t <= t_exit {
+            let (node_lower, node_size, value) = self.find_leaf(cell.x, cell.y, cell.z);
+            if !value.is_empty() {
+                return Some(RaycastHit {
+                    position: cell,
+                    distance: t,
+                    voxel: value,
+                });
+            }
+
+            // step out of the leaf through whichever side the ray reaches first
+            let mut exit_axis = 0;
+            let mut t_next = f32::INFINITY;
+            for axis in 0..3 {
+                let boundary = if direction[axis] > 0.0 {
+                    (node_lower[axis] + node_size) as f32
+                } else if direction[axis] < 0.0 {
+                    node_lower[axis] as f32
+                } else {
+                    continue;
+                };
+
+                let t_axis = (boundary - origin[axis]) / direction[axis];
+                if t_axis < t_next {
+                    t_next = t_axis;
+                    exit_axis = axis;
+                }
+            }
+
+            t = t.max(t_next);
+            let point = origin + direction * t;
+            for axis in 0..3 {
+                cell[axis] = if axis == exit_axis {
+                    if direction[axis] > 0.0 {
+                        node_lower[axis] + node_size
+                    } else {
+                        node_lower[axis] - 1
+                    }
+                } else {
+                    (point[axis].floor() as i32)
+                        .max(node_lower[axis])
+                        .min(node_lower[axis] + node_size - 1)
+                };
+            }
+        }
+
+        None
+    }
+}
+
+impl<T> Volume<T> for OctreeVolume<T>
+where
+    T: Voxel,
+{
+    fn get_region(&self) -> &Region {
+        &self.valid_region
+    }
+
+    fn get_voxel_at(&self, x: i32, y: i32, z: i32) -> T {
+        if self.valid_region.contains_point(x, y, z) {
+            self.find_leaf(x, y, z).2
+        } else {
+            self.border_value
+        }
+    }
+
+    fn set_voxel_at(&mut self, x: i32, y: i32, z: i32, voxel: T) -> Result<(), PositionError> {
+        if !self.valid_region.contains_point(x, y, z) {
+            return Err(PositionError {});
+        }
+
+        let lower = self.valid_region.get_lower_corner();
+        OctreeVolume::set_in_node(&mut self.root, lower, self.size, Vec3::new(x, y, z), voxel);
+
+        Ok(())
+    }
+
+    fn calculate_size_in_bytes(&self) -> usize {
+        std::mem::size_of::<Node<T>>() * self.root.count_nodes()
+    }
+
+    fn get_border_value(&self) -> T {
+        self.border_value
+    }
+}
+
+/// Extracts cubic meshes for each chunk of an octree volume, skipping uniform chunks.
+///
+/// A chunk produces no faces if it and the voxels bordering its negative sides all share one
+/// value, which the octree can tell without visiting the voxels. Only the remaining chunks are
+/// copied out and meshed.
+pub fn extract_octree_cubic_meshes<T>(
+    volume: &OctreeVolume<T>,
+    chunk_size: i32,
+    face_arity: Option<FaceArity>,
+    merge_quads: Option<bool>,
+) -> Option<Vec<ChunkMesh<T>>>
+where
+    T: Voxel,
+{
+    assert!(chunk_size > 0);
+
+    let region = volume.get_region();
+    let lower_chunk = region.get_lower_corner().map(|c| c.div_euclid(chunk_size));
+    let upper_chunk = region.get_upper_corner().map(|c| c.div_euclid(chunk_size));
+
+    let mut result = vec![];
+    for cz in lower_chunk.z..=upper_chunk.z {
+        for cy in lower_chunk.y..=upper_chunk.y {
+            for cx in lower_chunk.x..=upper_chunk.x {
+                let chunk = Vec3::new(cx, cy, cz);
+                let chunk_lower = chunk * chunk_size;
+                let chunk_region = match get_node_region(chunk_lower, chunk_size).intersect(region)
+                {
+                    Some(r) => r,
+                    None => continue,
+                };
+
+                let padded = Region::new(
+                    chunk_region.get_lower_corner() - 1,
+                    chunk_region.get_upper_corner(),
+                );
+                if volume.get_uniform_value(&padded).is_some() {
+                    continue;
+                }
+
+                let copy = volume.to_raw_volume_region(&padded);
+                let mesh = extract_cubic_mesh(
+                    &mut RawVolumeSampler::new(&copy),
+                    &chunk_region,
+                    face_arity,
+                    merge_quads,
+                )?;
+
+                result.push((chunk, mesh));
+            }
+        }
+    }
+
+    Some(result)
+}
@@ -0,0 +1,204 @@
+use std::io::{self, Read, Write};
+use vek::vec4::Vec4;
+
+/// Converts an sRGB encoded channel into linear intensity in `0.0..=1.0`.
+pub fn srgb_to_linear(value: u8) -> f32 {
+    let c = value as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear intensity in `0.0..=1.0` into an sRGB encoded channel.
+pub fn linear_to_srgb(value: f32) -> u8 {
+    let c = value.clamp(0.0, 1.0);
+    let encoded = if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+
+    (encoded * 255.0).round() as u8
+}
+
+/// A colour with 8 bits per channel.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Rgba { r, g, b, a }
+    }
+
+    /// Unpacks a colour stored with red in the lowest byte and alpha in the highest, as found in
+    /// `.vox` palettes.
+    pub fn from_abgr(packed: u32) -> Self {
+        Rgba {
+            r: packed as u8,
+            g: (packed >> 8) as u8,
+            b: (packed >> 16) as u8,
+            a: (packed >> 24) as u8,
+        }
+    }
+
+    pub fn to_abgr(self) -> u32 {
+        ((self.a as u32) << 24) | ((self.b as u32) << 16) | ((self.g as u32) << 8) | (self.r as u32)
+    }
+
+    /// Packs the colour with alpha in the highest byte and blue in the lowest.
+    pub fn to_argb(self) -> u32 {
+        ((self.a as u32) << 24) | ((self.r as u32) << 16) | ((self.g as u32) << 8) | (self.b as u32)
+    }
+
+    pub fn from_argb(packed: u32) -> Self {
+        Rgba {
+            r: (packed >> 16) as u8,
+            g: (packed >> 8) as u8,
+            b: packed as u8,
+            a: (packed >> 24) as u8,
+        }
+    }
+
+    /// Decodes the colour channels from sRGB to linear. Alpha is already linear and is only
+    /// rescaled.
+    pub fn to_linear(self) -> Vec4<f32> {
+        Vec4::new(
+            srgb_to_linear(self.r),
+            srgb_to_linear(self.g),
+            srgb_to_linear(self.b),
+            self.a as f32 / 255.0,
+        )
+    }
+
+    pub fn from_linear(colour: Vec4<f32>) -> Self {
+        Rgba {
+            r: linear_to_srgb(colour.x),
+            g: linear_to_srgb(colour.y),
+            b: linear_to_srgb(colour.z),
+            a: (colour.w.clamp(0.0, 1.0) * 255.0).round() as u8,
+        }
+    }
+
+    /// Formats the colour as `#rrggbbaa`.
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+    }
+
+    /// Parses `#rrggbbaa` or `#rrggbb`, with or without the leading `#`.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        let channel = |i: usize| u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok();
+
+        match hex.len() {
+            6 => Some(Rgba::new(channel(0)?, channel(1)?, channel(2)?, 255)),
+            8 => Some(Rgba::new(
+                channel(0)?,
+                channel(1)?,
+                channel(2)?,
+                channel(3)?,
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Maps voxel palette indices onto colours.
+///
+/// `.vox` files use 256 entries, but a palette may hold any number of colours. Colours are stored
+/// sRGB encoded, the same as in image files.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Palette {
+    colours: Vec<Rgba>,
+}
+
+impl Palette {
+    pub fn new(colours: Vec<Rgba>) -> Self {
+        Palette { colours }
+    }
+
+    /// Builds a palette from colours packed as by `Rgba::from_abgr`.
+    pub fn from_abgr(packed: &[u32]) -> Self {
+        Palette {
+            colours: packed.iter().map(|c| Rgba::from_abgr(*c)).collect(),
+        }
+    }
+
+    /// Returns the colour at `index`, or transparent black if the palette has no such entry.
+    pub fn get(&self, index: usize) -> Rgba {
+        self.colours.get(index).copied().unwrap_or_default()
+    }
+
+    /// Adds a colour to the end of the palette and returns its index.
+    pub fn push(&mut self, colour: Rgba) -> usize {
+        self.colours.push(colour);
+
+        self.colours.len() - 1
+    }
+
+    /// Finds the entry closest to `colour`, for quantising imported colours onto the palette.
+    ///
+    /// Distances are measured between the linear colours rather than the sRGB encoded values.
+    pub fn find_nearest(&self, colour: Rgba) -> Option<usize> {
+        let target = colour.to_linear();
+
+        self.colours
+            .iter()
+            .map(|c| (c.to_linear() - target).magnitude_squared())
+            .enumerate()
+            .fold(
+                None,
+                |best: Option<(usize, f32)>, (index, distance)| match best {
+                    Some((_, best_distance)) if best_distance <= distance => best,
+                    _ => Some((index, distance)),
+                },
+            )
+            .map(|(index, _)| index)
+    }
+
+    /// Writes the palette as four bytes per entry in `r, g, b, a` order.
+    pub fn write(&self, writer: &mut dyn Write) -> io::Result<()> {
+        for c in &self.colours {
+            writer.write_all(&[c.r, c.g, c.b, c.a])?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a palette written by `write`, consuming the reader to its end.
+    pub fn read(reader: &mut dyn Read) -> io::Result<Self> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        if bytes.len() % 4 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "palette data is not a whole number of colours",
+            ));
+        }
+
+        Ok(Palette {
+            colours: bytes
+                .chunks(4)
+                .map(|c| Rgba::new(c[0], c[1], c[2], c[3]))
+                .collect(),
+        })
+    }
+
+    pub fn colours(&self) -> &[Rgba] {
+        &self.colours
+    }
+
+    pub fn len(&self) -> usize {
+        self.colours.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colours.is_empty()
+    }
+}
@@ -0,0 +1,246 @@
+use crate::sampler::Sampler;
+use crate::voxel::Voxel;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use vek::vec3::Vec3;
+
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+const VERTICAL_COST: u32 = 10;
+
+/// The size and abilities of the agent a path is planned for, all measured in voxels.
+///
+/// Positions on a path are the cell the agent's feet are in. The agent occupies `agent_width`
+/// cells along x and z from there, and `agent_height` cells upwards.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PathOptions {
+    pub agent_width: i32,
+    pub agent_height: i32,
+    /// How far the agent can climb in one step.
+    pub step_height: i32,
+    /// How far the agent can drop in one step.
+    pub drop_height: i32,
+    pub allow_diagonal: bool,
+    /// Gives up after visiting this many cells.
+    pub max_visited: usize,
+}
+
+impl Default for PathOptions {
+    fn default() -> Self {
+        PathOptions {
+            agent_width: 1,
+            agent_height: 2,
+            step_height: 1,
+            drop_height: 3,
+            allow_diagonal: true,
+            max_visited: 100_000,
+        }
+    }
+}
+
+/// Tests if every cell from `lower_y` to `upper_y` is empty in each column under the agent.
+fn is_clear<T>(
+    sampler: &mut dyn Sampler<T>,
+    position: Vec3<i32>,
+    lower_y: i32,
+    upper_y: i32,
+    options: &PathOptions,
+) -> bool
+where
+    T: Voxel,
+{
+    for dz in 0..options.agent_width {
+        for dx in 0..options.agent_width {
+            sampler.set_position(position.x + dx, lower_y, position.z + dz);
+            for y in lower_y..=upper_y {
+                if !sampler.get_voxel().is_empty() {
+                    return false;
+                }
+                if y < upper_y {
+                    sampler.move_positive_y();
+                }
+            }
+        }
+    }
+
+    true
+}
+
+/// Tests if the agent can stand at `position`, with solid ground under every column it covers and
+/// room for its height above.
+pub fn is_walkable<T>(
+    sampler: &mut dyn Sampler<T>,
+    position: Vec3<i32>,
+    options: &PathOptions,
+) -> bool
+where
+    T: Voxel,
+{
+    for dz in 0..options.agent_width {
+        for dx in 0..options.agent_width {
+            sampler.set_position(position.x + dx, position.y, position.z + dz);
+            if sampler.peek(0, -1, 0).is_empty() {
+                return false;
+            }
+        }
+    }
+
+    is_clear(
+        sampler,
+        position,
+        position.y,
+        position.y + options.agent_height - 1,
+        options,
+    )
+}
+
+/// Finds where the agent ends up moving one cell from `position` along `dx` and `dz`.
+fn step<T>(
+    sampler: &mut dyn Sampler<T>,
+    position: Vec3<i32>,
+    dx: i32,
+    dz: i32,
+    options: &PathOptions,
+) -> Option<Vec3<i32>>
+where
+    T: Voxel,
+{
+    let level = Vec3::new(position.x + dx, position.y, position.z + dz);
+    let top = position.y + options.agent_height - 1;
+
+    let climbs = 1..=options.step_height;
+    let drops = (1..=options.drop_height).map(|dy| -dy);
+    for dy in std::iter::once(0).chain(climbs).chain(drops) {
+        let target = level + Vec3::new(0, dy, 0);
+        if !is_walkable(sampler, target, options) {
+            continue;
+        }
+
+        // the agent needs room to rise before stepping up, or to pass over the target before
+        // dropping onto it
+        let clear = match dy {
+            0 => true,
+            dy if dy > 0 => is_clear(sampler, position, top + 1, top + dy, options),
+            _ => is_clear(
+                sampler,
+                target,
+                target.y + options.agent_height,
+                top,
+                options,
+            ),
+        };
+        if clear {
+            return Some(target);
+        }
+    }
+
+    None
+}
+
+fn heuristic(from: Vec3<i32>, to: Vec3<i32>) -> u32 {
+    let d = (to - from).map(|c| c.unsigned_abs());
+    let (short, long) = (d.x.min(d.z), d.x.max(d.z));
+
+    short * DIAGONAL_COST + (long - short) * STRAIGHT_COST + d.y * VERTICAL_COST
+}
+
+/// Finds a path of walkable cells from `start` to `goal` with A*.
+///
+/// The path includes both ends. Diagonal moves are only taken on level ground where both of the
+/// straight moves they cut across are also possible. Returns `None` if either end is not walkable
+/// or no path is found within `options.max_visited` cells.
+pub fn find_path<T>(
+    sampler: &mut dyn Sampler<T>,
+    start: Vec3<i32>,
+    goal: Vec3<i32>,
+    options: &PathOptions,
+) -> Option<Vec<Vec3<i32>>>
+where
+    T: Voxel,
+{
+    if !is_walkable(sampler, start, options) || !is_walkable(sampler, goal, options) {
+        return None;
+    }
+
+    let key = |p: Vec3<i32>| (p.x, p.y, p.z);
+    let mut open = BinaryHeap::new();
+    let mut costs: HashMap<(i32, i32, i32), u32> = HashMap::new();
+    let mut came_from: HashMap<(i32, i32, i32), Vec3<i32>> = HashMap::new();
+
+    open.push(Reverse((heuristic(start, goal), 0, key(start))));
+    costs.insert(key(start), 0);
+
+    let mut visited = 0;
+    while let Some(Reverse((_, cost, current))) = open.pop() {
+        let position = Vec3::new(current.0, current.1, current.2);
+        if position == goal {
+            let mut path = vec![goal];
+            let mut at = goal;
+            while let Some(previous) = came_from.get(&key(at)) {
+                path.push(*previous);
+                at = *previous;
+            }
+            path.reverse();
+
+            return Some(path);
+        }
+
+        if costs.get(&current).is_some_and(|best| cost > *best) {
+            // a cheaper route to this cell has already been expanded
+            continue;
+        }
+
+        visited += 1;
+        if visited > options.max_visited {
+            return None;
+        }
+
+        let straight: Vec<(i32, i32, Option<Vec3<i32>>)> = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+            .iter()
+            .map(|(dx, dz)| (*dx, *dz, step(sampler, position, *dx, *dz, options)))
+            .collect();
+
+        let mut neighbours: Vec<(Vec3<i32>, u32)> = straight
+            .iter()
+            .filter_map(|(_, _, target)| *target)
+            .map(|target| {
+                let climb = (target.y - position.y).unsigned_abs();
+                (target, STRAIGHT_COST + climb * VERTICAL_COST)
+            })
+            .collect();
+
+        if options.allow_diagonal {
+            let is_level = |dx: i32, dz: i32| {
+                straight.iter().any(|(sx, sz, target)| {
+                    *sx == dx && *sz == dz && target.is_some_and(|t| t.y == position.y)
+                })
+            };
+
+            for (dx, dz) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                if !is_level(dx, 0) || !is_level(0, dz) {
+                    continue;
+                }
+
+                let target = position + Vec3::new(dx, 0, dz);
+                if is_walkable(sampler, target, options) {
+                    neighbours.push((target, DIAGONAL_COST));
+                }
+            }
+        }
+
+        for (target, step_cost) in neighbours {
+            let new_cost = cost + step_cost;
+            if costs.get(&key(target)).is_none_or(|best| new_cost < *best) {
+                costs.insert(key(target), new_cost);
+                came_from.insert(key(target), position);
+                open.push(Reverse((
+                    new_cost + heuristic(target, goal),
+                    new_cost,
+                    key(target),
+                )));
+            }
+        }
+    }
+
+    None
+}
@@ -1,6 +1,7 @@
 use crate::region::Region;
 use crate::volume::{PositionError, Volume};
 use crate::voxel::Voxel;
+use vek::vec3::Vec3;
 
 pub struct RawVolume<T>
 where
@@ -9,17 +10,19 @@ where
     pub data: Vec<T>,
     pub border_value: T,
     pub valid_region: Region,
+    modified: Option<Vec<Vec3<i32>>>,
 }
 
 impl<T> RawVolume<T>
 where
     T: Voxel,
 {
-    pub fn new(ref region: Region) -> Self {
+    pub fn new(region: Region) -> Self {
         RawVolume {
             data: vec![Default::default(); region.get_volume() as usize],
             border_value: Default::default(),
-            valid_region: region.clone(),
+            valid_region: region,
+            modified: None,
         }
     }
 
@@ -46,6 +49,79 @@ where
     pub fn set_border_value(&mut self, value: T) {
         self.border_value = value;
     }
+
+    /// Converts the volume to another voxel type, reusing its region.
+    ///
+    /// Modification tracking is not carried over to the new volume.
+    pub fn map_into<U, F>(self, f: F) -> RawVolume<U>
+    where
+        U: Voxel,
+        F: Fn(T) -> U,
+    {
+        RawVolume {
+            data: self.data.into_iter().map(&f).collect(),
+            border_value: f(self.border_value),
+            valid_region: self.valid_region,
+            modified: None,
+        }
+    }
+
+    /// Iterates over the position of every voxel in the volume along with a mutable reference to it.
+    ///
+    /// Writes made this way are not recorded by `set_track_modifications`.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vec3<i32>, &mut T)> {
+        self.valid_region.iter().zip(self.data.iter_mut())
+    }
+
+    /// Like `iter_mut`, but only visits the voxels inside `region`.
+    pub fn iter_region_mut(
+        &mut self,
+        region: &Region,
+    ) -> impl Iterator<Item = (Vec3<i32>, &mut T)> {
+        let valid_region = &self.valid_region;
+        let clipped = region.intersect(valid_region);
+        let width = valid_region.get_width() as usize;
+        let height = valid_region.get_height();
+        let lower = valid_region.get_lower_corner();
+
+        self.data
+            .chunks_mut(width)
+            .enumerate()
+            .filter_map(move |(row, voxels)| {
+                let clipped = clipped.as_ref()?;
+                let y = lower.y + row as i32 % height;
+                let z = lower.z + row as i32 / height;
+                if clipped.contains_point_in_y(y) && clipped.contains_point_in_z(z) {
+                    let start = (clipped.lower_x - lower.x) as usize;
+                    let end = (clipped.upper_x - lower.x) as usize;
+                    Some((clipped.lower_x, y, z, &mut voxels[start..=end]))
+                } else {
+                    None
+                }
+            })
+            .flat_map(|(lower_x, y, z, voxels)| {
+                voxels
+                    .iter_mut()
+                    .enumerate()
+                    .map(move |(i, voxel)| (Vec3::new(lower_x + i as i32, y, z), voxel))
+            })
+    }
+
+    /// Starts or stops recording the positions passed to `set_voxel_at`.
+    ///
+    /// Recorded positions are handed out by `take_modified_positions`. Stopping discards any
+    /// positions that have not been taken yet.
+    pub fn set_track_modifications(&mut self, enabled: bool) {
+        if !enabled {
+            self.modified = None;
+        } else if self.modified.is_none() {
+            self.modified = Some(vec![]);
+        }
+    }
+
+    pub fn is_tracking_modifications(&self) -> bool {
+        self.modified.is_some()
+    }
 }
 
 impl<T> Volume<T> for RawVolume<T>
@@ -65,6 +141,9 @@ where
         self.get_offset(x, y, z)
             .map_or(Err(PositionError {}), |offset| {
                 self.data[offset] = voxel;
+                if let Some(modified) = &mut self.modified {
+                    modified.push(Vec3::new(x, y, z));
+                }
                 Ok(())
             })
     }
@@ -76,4 +155,98 @@ where
     fn get_border_value(&self) -> T {
         self.border_value
     }
+
+    fn take_modified_positions(&mut self) -> Vec<Vec3<i32>> {
+        self.modified
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+}
+
+/// The serialized form of a `RawVolume`, with the voxels stored as runs of equal values.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct EncodedRawVolume<T> {
+    region: Region,
+    border_value: T,
+    runs: Vec<(u32, T)>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for RawVolume<T>
+where
+    T: Voxel + serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut runs: Vec<(u32, T)> = vec![];
+        for voxel in &self.data {
+            match runs.last_mut() {
+                Some((count, value)) if value == voxel => *count += 1,
+                _ => runs.push((1, *voxel)),
+            }
+        }
+
+        EncodedRawVolume {
+            region: self.valid_region.clone(),
+            border_value: self.border_value,
+            runs,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for RawVolume<T>
+where
+    T: Voxel + serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let encoded = EncodedRawVolume::<T>::deserialize(deserializer)?;
+        if !encoded.region.is_valid() {
+            return Err(D::Error::custom("invalid volume region"));
+        }
+
+        // the region comes from untrusted input, so its size is worked out without overflowing
+        // and limited to what `get_volume` can represent
+        let region = &encoded.region;
+        let size = [
+            (region.lower_x, region.upper_x),
+            (region.lower_y, region.upper_y),
+            (region.lower_z, region.upper_z),
+        ]
+        .iter()
+        .try_fold(1i64, |size, (lower, upper)| {
+            size.checked_mul(*upper as i64 - *lower as i64 + 1)
+        })
+        .filter(|size| *size <= i32::MAX as i64)
+        .ok_or_else(|| D::Error::custom("volume region is too large"))? as usize;
+
+        let mut data = vec![];
+        for (count, voxel) in encoded.runs {
+            match data.len().checked_add(count as usize) {
+                Some(total) if total <= size => {}
+                _ => return Err(D::Error::custom("too many voxels for the volume region")),
+            }
+            data.extend(std::iter::repeat_n(voxel, count as usize));
+        }
+        if data.len() != size {
+            return Err(D::Error::custom("too few voxels for the volume region"));
+        }
+
+        Ok(RawVolume {
+            data,
+            border_value: encoded.border_value,
+            valid_region: encoded.region,
+            modified: None,
+        })
+    }
 }
@@ -122,7 +122,7 @@ where
 
     fn move_positive_x(&mut self) {
         let was_valid = self.is_current_position_valid();
-        self.x_pos = self.x_pos + 1;
+        self.x_pos += 1;
         self.current_x_valid = self.valid_region.contains_point_in_x(self.x_pos);
         if was_valid && self.is_current_position_valid() {
             self.current_offset = Some(self.current_offset.unwrap() + 1);
@@ -133,7 +133,7 @@ where
 
     fn move_positive_y(&mut self) {
         let was_valid = self.is_current_position_valid();
-        self.y_pos = self.y_pos + 1;
+        self.y_pos += 1;
         self.current_y_valid = self.valid_region.contains_point_in_y(self.y_pos);
         if was_valid && self.is_current_position_valid() {
             self.current_offset =
@@ -145,7 +145,7 @@ where
 
     fn move_positive_z(&mut self) {
         let was_valid = self.is_current_position_valid();
-        self.z_pos = self.z_pos + 1;
+        self.z_pos += 1;
         self.current_z_valid = self.valid_region.contains_point_in_z(self.z_pos);
         if was_valid && self.is_current_position_valid() {
             self.current_offset =
@@ -157,7 +157,7 @@ where
 
     fn move_negative_x(&mut self) {
         let was_valid = self.is_current_position_valid();
-        self.x_pos = self.x_pos - 1;
+        self.x_pos -= 1;
         self.current_x_valid = self.valid_region.contains_point_in_x(self.x_pos);
         if was_valid && self.is_current_position_valid() {
             self.current_offset = Some(self.current_offset.unwrap() - 1);
@@ -168,7 +168,7 @@ where
 
     fn move_negative_y(&mut self) {
         let was_valid = self.is_current_position_valid();
-        self.y_pos = self.y_pos - 1;
+        self.y_pos -= 1;
         self.current_y_valid = self.valid_region.contains_point_in_y(self.y_pos);
         if was_valid && self.is_current_position_valid() {
             self.current_offset =
@@ -180,7 +180,7 @@ where
 
     fn move_negative_z(&mut self) {
         let was_valid = self.is_current_position_valid();
-        self.z_pos = self.z_pos - 1;
+        self.z_pos -= 1;
         self.current_z_valid = self.valid_region.contains_point_in_z(self.z_pos);
         if was_valid && self.is_current_position_valid() {
             self.current_offset =
@@ -194,315 +194,19 @@ where
         self.current_x_valid && self.current_y_valid && self.current_z_valid
     }
 
-    fn peek_voxel_1nx1ny1nz(&self) -> T {
+    fn peek(&self, dx: i32, dy: i32, dz: i32) -> T {
         if self.is_current_position_valid()
-            && self.can_go_neg_x(self.x_pos)
-            && self.can_go_neg_y(self.y_pos)
-            && self.can_go_neg_z(self.z_pos)
+            && (dx >= 0 || self.can_go_neg_x(self.x_pos))
+            && (dx <= 0 || self.can_go_pos_x(self.x_pos))
+            && (dy >= 0 || self.can_go_neg_y(self.y_pos))
+            && (dy <= 0 || self.can_go_pos_y(self.y_pos))
+            && (dz >= 0 || self.can_go_neg_z(self.z_pos))
+            && (dz <= 0 || self.can_go_pos_z(self.z_pos))
         {
             self.data[(self.current_offset.unwrap() as i32
-                - 1
-                - self.valid_region.get_width()
-                - self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1nx1ny0pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_neg_x(self.x_pos)
-            && self.can_go_neg_y(self.y_pos)
-        {
-            self.data
-                [(self.current_offset.unwrap() as i32 - 1 - self.valid_region.get_width()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1nx1ny1pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_neg_x(self.x_pos)
-            && self.can_go_neg_y(self.y_pos)
-            && self.can_go_pos_z(self.z_pos)
-        {
-            self.data[(self.current_offset.unwrap() as i32 - 1 - self.valid_region.get_width()
-                + self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1nx0py1nz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_neg_x(self.x_pos)
-            && self.can_go_neg_z(self.z_pos)
-        {
-            self.data
-                [(self.current_offset.unwrap() as i32 - 1 - self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1nx0py0pz(&self) -> T {
-        if self.is_current_position_valid() && self.can_go_neg_x(self.x_pos) {
-            self.data[(self.current_offset.unwrap() as i32 - 1) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1nx0py1pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_neg_x(self.x_pos)
-            && self.can_go_pos_z(self.z_pos)
-        {
-            self.data
-                [(self.current_offset.unwrap() as i32 - 1 + self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1nx1py1nz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_neg_x(self.x_pos)
-            && self.can_go_pos_y(self.y_pos)
-            && self.can_go_neg_z(self.z_pos)
-        {
-            self.data[(self.current_offset.unwrap() as i32 - 1 + self.valid_region.get_width()
-                - self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1nx1py0pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_neg_x(self.x_pos)
-            && self.can_go_pos_y(self.y_pos)
-        {
-            self.data
-                [(self.current_offset.unwrap() as i32 - 1 + self.valid_region.get_width()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1nx1py1pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_neg_x(self.x_pos)
-            && self.can_go_pos_y(self.y_pos)
-            && self.can_go_pos_z(self.z_pos)
-        {
-            self.data[(self.current_offset.unwrap() as i32 - 1
-                + self.valid_region.get_width()
-                + self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_0px1ny1nz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_neg_y(self.y_pos)
-            && self.can_go_neg_z(self.z_pos)
-        {
-            self.data[(self.current_offset.unwrap() as i32
-                - self.valid_region.get_width()
-                - self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_0px1ny0pz(&self) -> T {
-        if self.is_current_position_valid() && self.can_go_neg_y(self.y_pos) {
-            self.data
-                [(self.current_offset.unwrap() as i32 - self.valid_region.get_width()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_0px1ny1pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_neg_y(self.y_pos)
-            && self.can_go_pos_z(self.z_pos)
-        {
-            self.data[(self.current_offset.unwrap() as i32 - self.valid_region.get_width()
-                + self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_0px0py1nz(&self) -> T {
-        if self.is_current_position_valid() && self.can_go_neg_z(self.z_pos) {
-            self.data[(self.current_offset.unwrap() as i32 - self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_0px0py0pz(&self) -> T {
-        if self.is_current_position_valid() {
-            self.data[self.current_offset.unwrap()]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_0px0py1pz(&self) -> T {
-        if self.is_current_position_valid() && self.can_go_pos_z(self.z_pos) {
-            self.data[(self.current_offset.unwrap() as i32 + self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_0px1py1nz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_pos_y(self.y_pos)
-            && self.can_go_neg_z(self.z_pos)
-        {
-            self.data[(self.current_offset.unwrap() as i32 + self.valid_region.get_width()
-                - self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_0px1py0pz(&self) -> T {
-        if self.is_current_position_valid() && self.can_go_pos_y(self.y_pos) {
-            self.data
-                [(self.current_offset.unwrap() as i32 + self.valid_region.get_width()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_0px1py1pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_pos_y(self.y_pos)
-            && self.can_go_pos_z(self.z_pos)
-        {
-            self.data[(self.current_offset.unwrap() as i32
-                + self.valid_region.get_width()
-                + self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1px1ny1nz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_pos_x(self.x_pos)
-            && self.can_go_neg_y(self.y_pos)
-            && self.can_go_neg_z(self.z_pos)
-        {
-            self.data[(self.current_offset.unwrap() as i32 + 1
-                - self.valid_region.get_width()
-                - self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1px1ny0pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_pos_x(self.x_pos)
-            && self.can_go_neg_y(self.y_pos)
-        {
-            self.data
-                [(self.current_offset.unwrap() as i32 + 1 - self.valid_region.get_width()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1px1ny1pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_pos_x(self.x_pos)
-            && self.can_go_neg_y(self.y_pos)
-            && self.can_go_pos_z(self.z_pos)
-        {
-            self.data[(self.current_offset.unwrap() as i32 + 1 - self.valid_region.get_width()
-                + self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1px0py1nz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_pos_x(self.x_pos)
-            && self.can_go_neg_z(self.z_pos)
-        {
-            self.data
-                [(self.current_offset.unwrap() as i32 + 1 - self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1px0py0pz(&self) -> T {
-        if self.is_current_position_valid() && self.can_go_pos_x(self.x_pos) {
-            self.data[(self.current_offset.unwrap() as i32 + 1) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1px0py1pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_pos_x(self.x_pos)
-            && self.can_go_pos_z(self.z_pos)
-        {
-            self.data
-                [(self.current_offset.unwrap() as i32 + 1 + self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1px1py1nz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_pos_x(self.x_pos)
-            && self.can_go_pos_y(self.y_pos)
-            && self.can_go_neg_z(self.z_pos)
-        {
-            self.data[(self.current_offset.unwrap() as i32 + 1 + self.valid_region.get_width()
-                - self.valid_region.get_area()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1px1py0pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_pos_x(self.x_pos)
-            && self.can_go_pos_y(self.y_pos)
-        {
-            self.data
-                [(self.current_offset.unwrap() as i32 + 1 + self.valid_region.get_width()) as usize]
-        } else {
-            self.border_value
-        }
-    }
-
-    fn peek_voxel_1px1py1pz(&self) -> T {
-        if self.is_current_position_valid()
-            && self.can_go_pos_x(self.x_pos)
-            && self.can_go_pos_y(self.y_pos)
-            && self.can_go_pos_z(self.z_pos)
-        {
-            self.data[(self.current_offset.unwrap() as i32
-                + 1
-                + self.valid_region.get_width()
-                + self.valid_region.get_area()) as usize]
+                + dx
+                + dy * self.valid_region.get_width()
+                + dz * self.valid_region.get_area()) as usize]
         } else {
             self.border_value
         }
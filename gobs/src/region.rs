@@ -1,6 +1,7 @@
 use vek::vec3::Vec3;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Region {
     pub lower_x: i32,
     pub lower_y: i32,
@@ -90,6 +91,44 @@ impl Region {
         }
     }
 
+    pub fn get_upper_corner(&self) -> Vec3<i32> {
+        Vec3 {
+            x: self.upper_x,
+            y: self.upper_y,
+            z: self.upper_z,
+        }
+    }
+
+    /// Returns the region covered by both `self` and `other`, if they overlap at all.
+    pub fn intersect(&self, other: &Region) -> Option<Region> {
+        let result = Region {
+            lower_x: self.lower_x.max(other.lower_x),
+            lower_y: self.lower_y.max(other.lower_y),
+            lower_z: self.lower_z.max(other.lower_z),
+            upper_x: self.upper_x.min(other.upper_x),
+            upper_y: self.upper_y.min(other.upper_y),
+            upper_z: self.upper_z.min(other.upper_z),
+        };
+
+        if result.is_valid() {
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the region grown by `amount` voxels on every side, or shrunk if it is negative.
+    pub fn grow(&self, amount: i32) -> Region {
+        Region::new(
+            self.get_lower_corner() - amount,
+            self.get_upper_corner() + amount,
+        )
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.upper_x >= self.lower_x && self.upper_y >= self.lower_y && self.upper_z >= self.lower_z
+    }
+
     pub fn contains_point_in_x(&self, x: i32) -> bool {
         x >= self.lower_x && x <= self.upper_x
     }
@@ -101,4 +140,47 @@ impl Region {
     pub fn contains_point_in_z(&self, z: i32) -> bool {
         z >= self.lower_z && z <= self.upper_z
     }
+
+    /// Iterates over every position in the region, with x varying fastest and z slowest.
+    pub fn iter(&self) -> RegionIter {
+        RegionIter {
+            region: self.clone(),
+            next: if self.is_valid() {
+                Some(self.get_lower_corner())
+            } else {
+                None
+            },
+        }
+    }
+}
+
+pub struct RegionIter {
+    region: Region,
+    next: Option<Vec3<i32>>,
+}
+
+impl Iterator for RegionIter {
+    type Item = Vec3<i32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+
+        let mut next = current;
+        next.x += 1;
+        if next.x > self.region.upper_x {
+            next.x = self.region.lower_x;
+            next.y += 1;
+            if next.y > self.region.upper_y {
+                next.y = self.region.lower_y;
+                next.z += 1;
+            }
+        }
+        self.next = if next.z > self.region.upper_z {
+            None
+        } else {
+            Some(next)
+        };
+
+        Some(current)
+    }
 }
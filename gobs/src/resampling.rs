@@ -0,0 +1,132 @@
+use crate::raw_volume::RawVolume;
+use crate::region::Region;
+use crate::volume::Volume;
+use crate::voxel::{Density, Voxel};
+use vek::quaternion::Quaternion;
+use vek::vec3::Vec3;
+
+/// Works out the region of a volume scaled by `factor`, which keeps the lower corner of the
+/// original and is at least one voxel across.
+fn scaled_region(region: &Region, factor: Vec3<f32>) -> Region {
+    let lower = region.get_lower_corner();
+    let size = (region.get_upper_corner() - lower + 1).map(|c| c as f32) * factor;
+
+    Region::new(
+        lower,
+        lower + size.map(|c: f32| (c.round() as i32).max(1)) - 1,
+    )
+}
+
+/// Scales the volume by `factor` along each axis, taking each new voxel from the original voxel
+/// nearest to it.
+///
+/// Integer factors repeat each voxel that many times, and factors like 0.5 keep every second
+/// voxel. The result keeps the lower corner of the original region.
+pub fn scale_nearest<T>(volume: &dyn Volume<T>, factor: Vec3<f32>) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    let region = volume.get_region();
+    let lower = region.get_lower_corner();
+    let upper = region.get_upper_corner();
+
+    let mut result = RawVolume::new(scaled_region(region, factor));
+    result.set_border_value(volume.get_border_value());
+
+    for (p, voxel) in result.iter_mut() {
+        let local = ((p - lower).map(|c| c as f32) + 0.5) / factor;
+        let from = Vec3::partial_min(lower + local.map(|c| c.floor() as i32), upper);
+        *voxel = volume.get_voxel_at(from.x, from.y, from.z);
+    }
+
+    result
+}
+
+/// Scales the volume by `factor` along each axis, blending the eight original voxels around each
+/// new voxel.
+///
+/// This suits density or distance volumes rather than materials, where blending would invent
+/// materials which were never there. The result keeps the lower corner of the original region.
+pub fn scale_trilinear<T>(volume: &dyn Volume<T>, factor: Vec3<f32>) -> RawVolume<T>
+where
+    T: Density,
+{
+    let region = volume.get_region();
+    let lower = region.get_lower_corner();
+    let size = region.get_upper_corner() - lower + 1;
+
+    let mut result = RawVolume::new(scaled_region(region, factor));
+    result.set_border_value(volume.get_border_value());
+
+    for (p, voxel) in result.iter_mut() {
+        // voxel values are samples at their centres, and the edges are clamped
+        let local = ((p - lower).map(|c| c as f32) + 0.5) / factor - 0.5;
+        let local = local.map2(size, |c, s| c.clamp(0.0, (s - 1) as f32));
+        let base = local.map(|c| c.floor() as i32);
+        let t = (local - base.map(|c| c as f32)).map(|c| c as f64);
+
+        let sample = |dx: i32, dy: i32, dz: i32| {
+            let q = Vec3::partial_min(base + Vec3::new(dx, dy, dz), size - 1) + lower;
+            volume.get_voxel_at(q.x, q.y, q.z).to_f64()
+        };
+        let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+
+        let x00 = lerp(sample(0, 0, 0), sample(1, 0, 0), t.x);
+        let x10 = lerp(sample(0, 1, 0), sample(1, 1, 0), t.x);
+        let x01 = lerp(sample(0, 0, 1), sample(1, 0, 1), t.x);
+        let x11 = lerp(sample(0, 1, 1), sample(1, 1, 1), t.x);
+        let value = lerp(lerp(x00, x10, t.y), lerp(x01, x11, t.y), t.z);
+
+        *voxel = T::from_f64(value);
+    }
+
+    result
+}
+
+/// Rotates the volume by `rotation` about the voxel corner nearest the centre of its region, taking
+/// each new voxel from the original voxel nearest to it.
+///
+/// The result covers the whole of the rotated region, so it grows for angles which are not
+/// multiples of 90°. Voxels rotated in from outside the original region take its border value.
+pub fn rotate_nearest<T>(volume: &dyn Volume<T>, rotation: Quaternion<f32>) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    const EPSILON: f32 = 1e-3;
+
+    let rotation = rotation.normalized();
+    let region = volume.get_region();
+    let lower = region.get_lower_corner().map(|c| c as f32);
+    let upper = region.get_upper_corner().map(|c| c as f32) + 1.0;
+    // rotating about a voxel corner keeps quarter turns lined up with the voxels
+    let centre = ((lower + upper) * 0.5).map(|c: f32| c.floor());
+
+    let corners = Region::new(Vec3::zero(), Vec3::one())
+        .iter()
+        .map(|corner| {
+            let corner = lower + (upper - lower) * corner.map(|c| c as f32);
+            centre + rotation * (corner - centre)
+        })
+        .collect::<Vec<_>>();
+    let min = corners
+        .iter()
+        .fold(corners[0], |a, b| Vec3::partial_min(a, *b));
+    let max = corners
+        .iter()
+        .fold(corners[0], |a, b| Vec3::partial_max(a, *b));
+
+    let mut result = RawVolume::new(Region::new(
+        min.map(|c: f32| (c + EPSILON).floor() as i32),
+        max.map(|c: f32| (c - EPSILON).ceil() as i32 - 1),
+    ));
+    result.set_border_value(volume.get_border_value());
+
+    let inverse = rotation.conjugate();
+    for (p, voxel) in result.iter_mut() {
+        let from = centre + inverse * (p.map(|c| c as f32) + 0.5 - centre);
+        let from = from.map(|c| c.floor() as i32);
+        *voxel = volume.get_voxel_at(from.x, from.y, from.z);
+    }
+
+    result
+}
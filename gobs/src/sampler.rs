@@ -1,6 +1,12 @@
 use crate::voxel::Voxel;
 use vek::vec3::Vec3;
 
+/// Returns where the voxel at the given offset is stored in the result of `fetch_3x3x3`.
+#[inline]
+pub fn get_neighbourhood_index(dx: i32, dy: i32, dz: i32) -> usize {
+    ((dx + 1) + (dy + 1) * 3 + (dz + 1) * 9) as usize
+}
+
 pub trait Sampler<T>
 where
     T: Voxel,
@@ -18,33 +24,157 @@ where
 
     fn is_current_position_valid(&self) -> bool;
 
-    fn peek_voxel_1nx1ny1nz(&self) -> T;
-    fn peek_voxel_1nx1ny0pz(&self) -> T;
-    fn peek_voxel_1nx1ny1pz(&self) -> T;
-    fn peek_voxel_1nx0py1nz(&self) -> T;
-    fn peek_voxel_1nx0py0pz(&self) -> T;
-    fn peek_voxel_1nx0py1pz(&self) -> T;
-    fn peek_voxel_1nx1py1nz(&self) -> T;
-    fn peek_voxel_1nx1py0pz(&self) -> T;
-    fn peek_voxel_1nx1py1pz(&self) -> T;
-
-    fn peek_voxel_0px1ny1nz(&self) -> T;
-    fn peek_voxel_0px1ny0pz(&self) -> T;
-    fn peek_voxel_0px1ny1pz(&self) -> T;
-    fn peek_voxel_0px0py1nz(&self) -> T;
-    fn peek_voxel_0px0py0pz(&self) -> T;
-    fn peek_voxel_0px0py1pz(&self) -> T;
-    fn peek_voxel_0px1py1nz(&self) -> T;
-    fn peek_voxel_0px1py0pz(&self) -> T;
-    fn peek_voxel_0px1py1pz(&self) -> T;
-
-    fn peek_voxel_1px1ny1nz(&self) -> T;
-    fn peek_voxel_1px1ny0pz(&self) -> T;
-    fn peek_voxel_1px1ny1pz(&self) -> T;
-    fn peek_voxel_1px0py1nz(&self) -> T;
-    fn peek_voxel_1px0py0pz(&self) -> T;
-    fn peek_voxel_1px0py1pz(&self) -> T;
-    fn peek_voxel_1px1py1nz(&self) -> T;
-    fn peek_voxel_1px1py0pz(&self) -> T;
-    fn peek_voxel_1px1py1pz(&self) -> T;
+    /// Returns the voxel at an offset from the current position. Each offset is in `-1..=1`.
+    fn peek(&self, dx: i32, dy: i32, dz: i32) -> T;
+
+    /// Returns the 3×3×3 block of voxels around the current position, with x varying fastest.
+    ///
+    /// Use `get_neighbourhood_index` to find a given offset in the result.
+    fn fetch_3x3x3(&self) -> [T; 27] {
+        let mut result = [self.get_voxel(); 27];
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    result[get_neighbourhood_index(dx, dy, dz)] = self.peek(dx, dy, dz);
+                }
+            }
+        }
+
+        result
+    }
+
+    #[inline]
+    fn peek_voxel_1nx1ny1nz(&self) -> T {
+        self.peek(-1, -1, -1)
+    }
+
+    #[inline]
+    fn peek_voxel_1nx1ny0pz(&self) -> T {
+        self.peek(-1, -1, 0)
+    }
+
+    #[inline]
+    fn peek_voxel_1nx1ny1pz(&self) -> T {
+        self.peek(-1, -1, 1)
+    }
+
+    #[inline]
+    fn peek_voxel_1nx0py1nz(&self) -> T {
+        self.peek(-1, 0, -1)
+    }
+
+    #[inline]
+    fn peek_voxel_1nx0py0pz(&self) -> T {
+        self.peek(-1, 0, 0)
+    }
+
+    #[inline]
+    fn peek_voxel_1nx0py1pz(&self) -> T {
+        self.peek(-1, 0, 1)
+    }
+
+    #[inline]
+    fn peek_voxel_1nx1py1nz(&self) -> T {
+        self.peek(-1, 1, -1)
+    }
+
+    #[inline]
+    fn peek_voxel_1nx1py0pz(&self) -> T {
+        self.peek(-1, 1, 0)
+    }
+
+    #[inline]
+    fn peek_voxel_1nx1py1pz(&self) -> T {
+        self.peek(-1, 1, 1)
+    }
+
+    #[inline]
+    fn peek_voxel_0px1ny1nz(&self) -> T {
+        self.peek(0, -1, -1)
+    }
+
+    #[inline]
+    fn peek_voxel_0px1ny0pz(&self) -> T {
+        self.peek(0, -1, 0)
+    }
+
+    #[inline]
+    fn peek_voxel_0px1ny1pz(&self) -> T {
+        self.peek(0, -1, 1)
+    }
+
+    #[inline]
+    fn peek_voxel_0px0py1nz(&self) -> T {
+        self.peek(0, 0, -1)
+    }
+
+    #[inline]
+    fn peek_voxel_0px0py0pz(&self) -> T {
+        self.peek(0, 0, 0)
+    }
+
+    #[inline]
+    fn peek_voxel_0px0py1pz(&self) -> T {
+        self.peek(0, 0, 1)
+    }
+
+    #[inline]
+    fn peek_voxel_0px1py1nz(&self) -> T {
+        self.peek(0, 1, -1)
+    }
+
+    #[inline]
+    fn peek_voxel_0px1py0pz(&self) -> T {
+        self.peek(0, 1, 0)
+    }
+
+    #[inline]
+    fn peek_voxel_0px1py1pz(&self) -> T {
+        self.peek(0, 1, 1)
+    }
+
+    #[inline]
+    fn peek_voxel_1px1ny1nz(&self) -> T {
+        self.peek(1, -1, -1)
+    }
+
+    #[inline]
+    fn peek_voxel_1px1ny0pz(&self) -> T {
+        self.peek(1, -1, 0)
+    }
+
+    #[inline]
+    fn peek_voxel_1px1ny1pz(&self) -> T {
+        self.peek(1, -1, 1)
+    }
+
+    #[inline]
+    fn peek_voxel_1px0py1nz(&self) -> T {
+        self.peek(1, 0, -1)
+    }
+
+    #[inline]
+    fn peek_voxel_1px0py0pz(&self) -> T {
+        self.peek(1, 0, 0)
+    }
+
+    #[inline]
+    fn peek_voxel_1px0py1pz(&self) -> T {
+        self.peek(1, 0, 1)
+    }
+
+    #[inline]
+    fn peek_voxel_1px1py1nz(&self) -> T {
+        self.peek(1, 1, -1)
+    }
+
+    #[inline]
+    fn peek_voxel_1px1py0pz(&self) -> T {
+        self.peek(1, 1, 0)
+    }
+
+    #[inline]
+    fn peek_voxel_1px1py1pz(&self) -> T {
+        self.peek(1, 1, 1)
+    }
 }
@@ -0,0 +1,167 @@
+use vek::vec3::Vec3;
+
+/// A signed distance function, which is negative inside the shape and positive outside it.
+///
+/// Distances are measured in voxels. Shapes can be combined with `union`, `intersection`,
+/// `difference` and `translate`, and any `Fn(Vec3<f32>) -> f32` can be used as a shape.
+pub trait Sdf {
+    fn distance(&self, position: Vec3<f32>) -> f32;
+
+    fn union<S>(self, other: S) -> Union<Self, S>
+    where
+        Self: Sized,
+        S: Sdf,
+    {
+        Union(self, other)
+    }
+
+    fn intersection<S>(self, other: S) -> Intersection<Self, S>
+    where
+        Self: Sized,
+        S: Sdf,
+    {
+        Intersection(self, other)
+    }
+
+    /// Cuts `other` out of this shape.
+    fn difference<S>(self, other: S) -> Difference<Self, S>
+    where
+        Self: Sized,
+        S: Sdf,
+    {
+        Difference(self, other)
+    }
+
+    fn translate(self, offset: Vec3<f32>) -> Translate<Self>
+    where
+        Self: Sized,
+    {
+        Translate(self, offset)
+    }
+}
+
+impl<F> Sdf for F
+where
+    F: Fn(Vec3<f32>) -> f32,
+{
+    fn distance(&self, position: Vec3<f32>) -> f32 {
+        self(position)
+    }
+}
+
+pub struct Sphere {
+    pub centre: Vec3<f32>,
+    pub radius: f32,
+}
+
+impl Sdf for Sphere {
+    fn distance(&self, position: Vec3<f32>) -> f32 {
+        (position - self.centre).magnitude() - self.radius
+    }
+}
+
+/// An axis aligned box.
+pub struct Cuboid {
+    pub centre: Vec3<f32>,
+    pub half_extents: Vec3<f32>,
+}
+
+impl Sdf for Cuboid {
+    fn distance(&self, position: Vec3<f32>) -> f32 {
+        let q = (position - self.centre).map(f32::abs) - self.half_extents;
+        let outside = q.map(|c| c.max(0.0)).magnitude();
+        let inside = q.x.max(q.y).max(q.z).min(0.0);
+
+        outside + inside
+    }
+}
+
+/// A cylinder standing along the y axis.
+pub struct Cylinder {
+    pub centre: Vec3<f32>,
+    pub radius: f32,
+    pub half_height: f32,
+}
+
+impl Sdf for Cylinder {
+    fn distance(&self, position: Vec3<f32>) -> f32 {
+        let p = position - self.centre;
+        let dr = (p.x * p.x + p.z * p.z).sqrt() - self.radius;
+        let dy = p.y.abs() - self.half_height;
+        let outside = (dr.max(0.0).powi(2) + dy.max(0.0).powi(2)).sqrt();
+
+        outside + dr.max(dy).min(0.0)
+    }
+}
+
+/// Everything below the plane through `point` facing along `normal`, which must be normalized.
+pub struct Plane {
+    pub point: Vec3<f32>,
+    pub normal: Vec3<f32>,
+}
+
+impl Sdf for Plane {
+    fn distance(&self, position: Vec3<f32>) -> f32 {
+        (position - self.point).dot(self.normal)
+    }
+}
+
+pub struct Union<A, B>(pub A, pub B);
+
+impl<A, B> Sdf for Union<A, B>
+where
+    A: Sdf,
+    B: Sdf,
+{
+    fn distance(&self, position: Vec3<f32>) -> f32 {
+        self.0.distance(position).min(self.1.distance(position))
+    }
+}
+
+pub struct Intersection<A, B>(pub A, pub B);
+
+impl<A, B> Sdf for Intersection<A, B>
+where
+    A: Sdf,
+    B: Sdf,
+{
+    fn distance(&self, position: Vec3<f32>) -> f32 {
+        self.0.distance(position).max(self.1.distance(position))
+    }
+}
+
+pub struct Difference<A, B>(pub A, pub B);
+
+impl<A, B> Sdf for Difference<A, B>
+where
+    A: Sdf,
+    B: Sdf,
+{
+    fn distance(&self, position: Vec3<f32>) -> f32 {
+        self.0.distance(position).max(-self.1.distance(position))
+    }
+}
+
+pub struct Translate<S>(pub S, pub Vec3<f32>);
+
+impl<S> Sdf for Translate<S>
+where
+    S: Sdf,
+{
+    fn distance(&self, position: Vec3<f32>) -> f32 {
+        self.0.distance(position - self.1)
+    }
+}
+
+/// A minimum which rounds off the crease where `a` and `b` meet, over a distance of about `blend`.
+///
+/// With a `blend` of zero or less this is the ordinary minimum.
+pub fn smooth_min(a: f32, b: f32, blend: f32) -> f32 {
+    if blend <= 0.0 {
+        return a.min(b);
+    }
+
+    let h = (0.5 + 0.5 * (b - a) / blend).clamp(0.0, 1.0);
+
+    b + (a - b) * h - blend * h * (1.0 - h)
+}
@@ -0,0 +1,310 @@
+use crate::raw_volume::RawVolume;
+use crate::region::Region;
+use crate::sdf::{smooth_min, Sdf};
+use crate::volume::{PositionError, Volume};
+use crate::voxel::Voxel;
+use vek::vec3::Vec3;
+
+/// A voxel type which can store a signed distance.
+///
+/// Integer types quantise distances in `-max_distance..=max_distance` onto their whole range,
+/// trading precision for memory. Distances past `max_distance` are clamped.
+pub trait Distance: Voxel {
+    fn to_distance(self, max_distance: f32) -> f32;
+    fn from_distance(distance: f32, max_distance: f32) -> Self;
+}
+
+impl Distance for f32 {
+    fn to_distance(self, _max_distance: f32) -> f32 {
+        self
+    }
+
+    fn from_distance(distance: f32, max_distance: f32) -> Self {
+        distance.clamp(-max_distance, max_distance)
+    }
+}
+
+macro_rules! impl_quantised_distance {
+    ($($t:ty),*) => {
+        $(
+            impl Distance for $t {
+                fn to_distance(self, max_distance: f32) -> f32 {
+                    self as f32 / <$t>::MAX as f32 * max_distance
+                }
+
+                fn from_distance(distance: f32, max_distance: f32) -> Self {
+                    let scaled = (distance / max_distance).clamp(-1.0, 1.0);
+                    (scaled * <$t>::MAX as f32).round() as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_quantised_distance!(i8, i16);
+
+/// A volume of signed distances to a surface, negative inside and positive outside.
+///
+/// Distances are measured in voxels from the voxel centres. Positions outside the volume are
+/// treated as being `max_distance` outside the surface.
+pub struct SdfVolume<T>
+where
+    T: Distance,
+{
+    volume: RawVolume<T>,
+    max_distance: f32,
+}
+
+/// Calculates the squared distance from every sample to the nearest zero sample along one line,
+/// using the lower envelope of parabolas from Felzenszwalb and Huttenlocher.
+fn distance_transform_line(f: &[f64], d: &mut [f64], v: &mut [usize], z: &mut [f64]) {
+    let n = f.len();
+    let parabola = |q: usize| f[q] + (q * q) as f64;
+
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = f64::NEG_INFINITY;
+    z[1] = f64::INFINITY;
+
+    for q in 1..n {
+        let mut s = (parabola(q) - parabola(v[k])) / (2 * q - 2 * v[k]) as f64;
+        while s <= z[k] {
+            k -= 1;
+            s = (parabola(q) - parabola(v[k])) / (2 * q - 2 * v[k]) as f64;
+        }
+
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f64::INFINITY;
+    }
+
+    k = 0;
+    for (q, distance) in d.iter_mut().enumerate().take(n) {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let offset = q as f64 - v[k] as f64;
+        *distance = offset * offset + f[v[k]];
+    }
+}
+
+/// Calculates the squared distance from every cell of the grid to the nearest cell equal to
+/// `target`.
+fn distance_transform(grid: &[bool], size: Vec3<usize>, target: bool) -> Vec<f64> {
+    const FAR: f64 = 1e20;
+
+    let mut distances: Vec<f64> = grid
+        .iter()
+        .map(|cell| if *cell == target { 0.0 } else { FAR })
+        .collect();
+
+    let longest = size.x.max(size.y).max(size.z);
+    let mut f = vec![0.0; longest];
+    let mut d = vec![0.0; longest];
+    let mut v = vec![0; longest];
+    let mut z = vec![0.0; longest + 1];
+
+    let strides = [1, size.x, size.x * size.y];
+    for axis in 0..3 {
+        let n = size[axis];
+        let stride = strides[axis];
+
+        let starts = (0..distances.len() / (n * stride))
+            .flat_map(|outer| (0..stride).map(move |inner| outer * n * stride + inner));
+        for start in starts {
+            for i in 0..n {
+                f[i] = distances[start + i * stride];
+            }
+            distance_transform_line(&f[..n], &mut d[..n], &mut v[..n], &mut z[..=n]);
+            for i in 0..n {
+                distances[start + i * stride] = d[i];
+            }
+        }
+    }
+
+    distances
+}
+
+impl<T> SdfVolume<T>
+where
+    T: Distance,
+{
+    /// Creates a volume with every voxel `max_distance` outside the surface.
+    pub fn new(region: Region, max_distance: f32) -> Self {
+        assert!(max_distance > 0.0);
+
+        let outside = T::from_distance(max_distance, max_distance);
+        let mut volume = RawVolume::new(region);
+        volume.set_border_value(outside);
+        for (_, voxel) in volume.iter_mut() {
+            *voxel = outside;
+        }
+
+        SdfVolume {
+            volume,
+            max_distance,
+        }
+    }
+
+    /// Samples `shape` at the centre of every voxel of `region`.
+    pub fn from_sdf(region: Region, shape: &dyn Sdf, max_distance: f32) -> Self {
+        let mut result = SdfVolume::new(region, max_distance);
+        for (position, voxel) in result.volume.iter_mut() {
+            let distance = shape.distance(position.map(|c| c as f32 + 0.5));
+            *voxel = T::from_distance(distance, max_distance);
+        }
+
+        result
+    }
+
+    /// Builds the distance field of the non-empty voxels of `volume`.
+    ///
+    /// The surface is placed halfway between the centres of empty and non-empty voxels, and
+    /// everything outside `volume` counts as empty.
+    pub fn from_occupancy<U>(volume: &RawVolume<U>, max_distance: f32) -> Self
+    where
+        U: Voxel,
+    {
+        let region = volume.get_region().clone();
+        let mut result = SdfVolume::new(region.clone(), max_distance);
+
+        // pad the grid with a layer of empty cells so voxels on the edge of the volume are close
+        // to an empty cell
+        let lower = region.get_lower_corner() - 1;
+        let size = Vec3::new(
+            region.get_width() as usize + 2,
+            region.get_height() as usize + 2,
+            region.get_depth() as usize + 2,
+        );
+        let padded = Region::new(lower, region.get_upper_corner() + 1);
+        let grid: Vec<bool> = padded
+            .iter()
+            .map(|p| {
+                region.contains_point(p.x, p.y, p.z)
+                    && !volume.get_voxel_at(p.x, p.y, p.z).is_empty()
+            })
+            .collect();
+
+        let to_occupied = distance_transform(&grid, size, true);
+        let to_empty = distance_transform(&grid, size, false);
+
+        for (position, voxel) in result.volume.iter_mut() {
+            let local = (position - lower).map(|c| c as usize);
+            let index = local.x + local.y * size.x + local.z * size.x * size.y;
+            let distance = if grid[index] {
+                0.5 - to_empty[index].sqrt()
+            } else {
+                to_occupied[index].sqrt() - 0.5
+            };
+
+            *voxel = T::from_distance(distance as f32, max_distance);
+        }
+
+        result
+    }
+
+    pub fn get_max_distance(&self) -> f32 {
+        self.max_distance
+    }
+
+    pub fn get_distance(&self, x: i32, y: i32, z: i32) -> f32 {
+        self.volume
+            .get_voxel_at(x, y, z)
+            .to_distance(self.max_distance)
+    }
+
+    pub fn set_distance(
+        &mut self,
+        x: i32,
+        y: i32,
+        z: i32,
+        distance: f32,
+    ) -> Result<(), PositionError> {
+        self.volume
+            .set_voxel_at(x, y, z, T::from_distance(distance, self.max_distance))
+    }
+
+    pub fn is_inside(&self, x: i32, y: i32, z: i32) -> bool {
+        self.get_distance(x, y, z) < 0.0
+    }
+
+    fn combine<U, F>(&mut self, other: &SdfVolume<U>, f: F)
+    where
+        U: Distance,
+        F: Fn(f32, f32) -> f32,
+    {
+        let max_distance = self.max_distance;
+        for (position, voxel) in self.volume.iter_mut() {
+            let a = voxel.to_distance(max_distance);
+            let b = other.get_distance(position.x, position.y, position.z);
+            *voxel = T::from_distance(f(a, b), max_distance);
+        }
+    }
+
+    /// Adds the shape in `other` to this one, rounding the seams over about `blend` voxels.
+    ///
+    /// The volumes are combined where they overlap in space, and this volume keeps its region.
+    pub fn union<U>(&mut self, other: &SdfVolume<U>, blend: f32)
+    where
+        U: Distance,
+    {
+        self.combine(other, |a, b| smooth_min(a, b, blend));
+    }
+
+    /// Cuts the shape in `other` out of this one.
+    pub fn subtract<U>(&mut self, other: &SdfVolume<U>, blend: f32)
+    where
+        U: Distance,
+    {
+        self.combine(other, |a, b| -smooth_min(-a, b, blend));
+    }
+
+    /// Keeps only the parts of this shape which are also inside `other`.
+    pub fn intersect<U>(&mut self, other: &SdfVolume<U>, blend: f32)
+    where
+        U: Distance,
+    {
+        self.combine(other, |a, b| -smooth_min(-a, -b, blend));
+    }
+
+    /// Fills the voxels inside the surface with `material`, for use with the cubic extractor.
+    pub fn to_raw_volume<U>(&self, material: U) -> RawVolume<U>
+    where
+        U: Voxel,
+    {
+        let max_distance = self.max_distance;
+        self.volume.map(|v| {
+            if v.to_distance(max_distance) < 0.0 {
+                material
+            } else {
+                Default::default()
+            }
+        })
+    }
+}
+
+impl<T> Volume<T> for SdfVolume<T>
+where
+    T: Distance,
+{
+    fn get_region(&self) -> &Region {
+        self.volume.get_region()
+    }
+
+    fn get_voxel_at(&self, x: i32, y: i32, z: i32) -> T {
+        self.volume.get_voxel_at(x, y, z)
+    }
+
+    fn set_voxel_at(&mut self, x: i32, y: i32, z: i32, voxel: T) -> Result<(), PositionError> {
+        self.volume.set_voxel_at(x, y, z, voxel)
+    }
+
+    fn calculate_size_in_bytes(&self) -> usize {
+        self.volume.calculate_size_in_bytes()
+    }
+
+    fn get_border_value(&self) -> T {
+        self.volume.get_border_value()
+    }
+}
@@ -0,0 +1,181 @@
+use crate::region::Region;
+use crate::volume::{PositionError, Volume};
+use crate::volume_sampler::VolumeSampler;
+use crate::voxel::Voxel;
+use std::collections::HashMap;
+use vek::vec3::Vec3;
+
+const BRICK_SHIFT: i32 = 3;
+const BRICK_SIZE: i32 = 1 << BRICK_SHIFT;
+const BRICK_MASK: i32 = BRICK_SIZE - 1;
+const BRICK_VOLUME: usize = (BRICK_SIZE * BRICK_SIZE * BRICK_SIZE) as usize;
+
+/// The largest coordinate magnitude of the region used by `SparseVolume::new`.
+///
+/// The region runs from `-SPARSE_VOLUME_EXTENT` to `SPARSE_VOLUME_EXTENT`, so its width, height and
+/// depth are `2^31 - 1`, the largest that fits in an `i32`. Note that `get_volume` of such a region
+/// does not fit.
+pub const SPARSE_VOLUME_EXTENT: i32 = (1 << 30) - 1;
+
+/// A volume which only stores the parts of space that contain non-empty voxels.
+///
+/// Space is divided into bricks of 8×8×8 voxels which are kept in a hash map and only allocated
+/// once a non-empty voxel is written to them. Memory use depends on how many bricks are touched,
+/// not on the size of the region.
+///
+/// Use `iter_occupied` rather than the `Volume` iterators, which visit every position of the
+/// region however large it is.
+pub struct SparseVolume<T>
+where
+    T: Voxel,
+{
+    bricks: HashMap<Vec3<i32>, Vec<T>>,
+    border_value: T,
+    valid_region: Region,
+}
+
+impl<T> SparseVolume<T>
+where
+    T: Voxel,
+{
+    /// Creates a volume covering `±SPARSE_VOLUME_EXTENT` along each axis.
+    pub fn new() -> Self {
+        SparseVolume::with_region(Region::new(
+            Vec3::broadcast(-SPARSE_VOLUME_EXTENT),
+            Vec3::broadcast(SPARSE_VOLUME_EXTENT),
+        ))
+    }
+
+    pub fn with_region(region: Region) -> Self {
+        SparseVolume {
+            bricks: HashMap::new(),
+            border_value: Default::default(),
+            valid_region: region,
+        }
+    }
+
+    pub fn set_border_value(&mut self, value: T) {
+        self.border_value = value;
+    }
+
+    fn get_brick_key(x: i32, y: i32, z: i32) -> Vec3<i32> {
+        Vec3::new(x >> BRICK_SHIFT, y >> BRICK_SHIFT, z >> BRICK_SHIFT)
+    }
+
+    fn get_brick_offset(x: i32, y: i32, z: i32) -> usize {
+        ((x & BRICK_MASK)
+            + ((y & BRICK_MASK) << BRICK_SHIFT)
+            + ((z & BRICK_MASK) << (BRICK_SHIFT * 2))) as usize
+    }
+
+    pub fn get_brick_count(&self) -> usize {
+        self.bricks.len()
+    }
+
+    /// Counts the non-empty voxels in the volume.
+    pub fn get_occupied_count(&self) -> usize {
+        self.bricks
+            .values()
+            .map(|brick| brick.iter().filter(|v| !v.is_empty()).count())
+            .sum()
+    }
+
+    /// Iterates over the non-empty voxels in the volume, in no particular order.
+    pub fn iter_occupied(&self) -> impl Iterator<Item = (Vec3<i32>, T)> + '_ {
+        self.bricks.iter().flat_map(|(key, brick)| {
+            let lower = *key * BRICK_SIZE;
+            brick
+                .iter()
+                .enumerate()
+                .filter(|(_, voxel)| !voxel.is_empty())
+                .map(move |(offset, voxel)| {
+                    let offset = offset as i32;
+                    let local = Vec3::new(
+                        offset & BRICK_MASK,
+                        (offset >> BRICK_SHIFT) & BRICK_MASK,
+                        offset >> (BRICK_SHIFT * 2),
+                    );
+                    (lower + local, *voxel)
+                })
+        })
+    }
+
+    /// Calculates the smallest region containing every non-empty voxel.
+    pub fn calculate_occupied_region(&self) -> Option<Region> {
+        let mut positions = self.iter_occupied().map(|(position, _)| position);
+        let first = positions.next()?;
+        let (lower, upper) = positions.fold((first, first), |(lower, upper), position| {
+            (Vec3::min(lower, position), Vec3::max(upper, position))
+        });
+
+        Some(Region::new(lower, upper))
+    }
+}
+
+impl<T> Default for SparseVolume<T>
+where
+    T: Voxel,
+{
+    fn default() -> Self {
+        SparseVolume::new()
+    }
+}
+
+impl<T> Volume<T> for SparseVolume<T>
+where
+    T: Voxel,
+{
+    fn get_region(&self) -> &Region {
+        &self.valid_region
+    }
+
+    fn get_voxel_at(&self, x: i32, y: i32, z: i32) -> T {
+        if !self.valid_region.contains_point(x, y, z) {
+            return self.border_value;
+        }
+
+        self.bricks
+            .get(&SparseVolume::<T>::get_brick_key(x, y, z))
+            .map_or(Default::default(), |brick| {
+                brick[SparseVolume::<T>::get_brick_offset(x, y, z)]
+            })
+    }
+
+    fn set_voxel_at(&mut self, x: i32, y: i32, z: i32, voxel: T) -> Result<(), PositionError> {
+        if !self.valid_region.contains_point(x, y, z) {
+            return Err(PositionError {});
+        }
+
+        let key = SparseVolume::<T>::get_brick_key(x, y, z);
+        let offset = SparseVolume::<T>::get_brick_offset(x, y, z);
+
+        if voxel.is_empty() {
+            if let Some(brick) = self.bricks.get_mut(&key) {
+                brick[offset] = voxel;
+                if brick.iter().all(|v| v.is_empty()) {
+                    self.bricks.remove(&key);
+                }
+            }
+        } else {
+            self.bricks
+                .entry(key)
+                .or_insert_with(|| vec![Default::default(); BRICK_VOLUME])[offset] = voxel;
+        }
+
+        Ok(())
+    }
+
+    fn calculate_size_in_bytes(&self) -> usize {
+        self.bricks.len()
+            * (std::mem::size_of::<Vec3<i32>>()
+                + std::mem::size_of::<Vec<T>>()
+                + std::mem::size_of::<T>() * BRICK_VOLUME)
+    }
+
+    fn get_border_value(&self) -> T {
+        self.border_value
+    }
+}
+
+/// Samples a `SparseVolume` through the generic `VolumeSampler`.
+pub type SparseVolumeSampler<'a, T> = VolumeSampler<'a, T, SparseVolume<T>>;
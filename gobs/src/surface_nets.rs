@@ -0,0 +1,289 @@
+use crate::mesh::{FaceArity, Mesh};
+use crate::region::Region;
+use crate::sampler::Sampler;
+use crate::vertex::Vertex;
+use crate::voxel::{Density, Voxel};
+use vek::vec3::Vec3;
+
+/// How the vertex inside each cell crossed by the surface is positioned.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VertexPlacement {
+    /// Average the points where the surface crosses the edges of the cell, as in surface nets.
+    /// This gives smooth meshes but rounds off corners.
+    Average,
+    /// Minimise the quadratic error function of the planes through those points, as in dual
+    /// contouring. This keeps sharp edges and corners.
+    Qef,
+}
+
+const CORNERS: [Vec3<f32>; 8] = [
+    Vec3::new(0.0, 0.0, 0.0),
+    Vec3::new(1.0, 0.0, 0.0),
+    Vec3::new(0.0, 1.0, 0.0),
+    Vec3::new(1.0, 1.0, 0.0),
+    Vec3::new(0.0, 0.0, 1.0),
+    Vec3::new(1.0, 0.0, 1.0),
+    Vec3::new(0.0, 1.0, 1.0),
+    Vec3::new(1.0, 1.0, 1.0),
+];
+
+/// Blends the values at the corners of a cell trilinearly.
+fn trilinear<V>(values: &[V; 8], p: Vec3<f32>) -> V
+where
+    V: Copy + std::ops::Mul<f32, Output = V> + std::ops::Add<Output = V>,
+{
+    CORNERS
+        .iter()
+        .zip(values.iter())
+        .map(|(corner, value)| {
+            let weight = corner.map2(p, |c, p| if c > 0.0 { p } else { 1.0 - p });
+            *value * (weight.x * weight.y * weight.z)
+        })
+        .reduce(|a, b| a + b)
+        .unwrap()
+}
+
+/// Estimates the gradient of the density at the sampler's position from its neighbours.
+fn get_gradient<T, F>(sampler: &dyn Sampler<T>, density: &F) -> Vec3<f32>
+where
+    T: Voxel,
+    F: Fn(T) -> f32,
+{
+    Vec3::new(
+        density(sampler.peek(1, 0, 0)) - density(sampler.peek(-1, 0, 0)),
+        density(sampler.peek(0, 1, 0)) - density(sampler.peek(0, -1, 0)),
+        density(sampler.peek(0, 0, 1)) - density(sampler.peek(0, 0, -1)),
+    ) * 0.5
+}
+
+fn determinant(m: &[Vec3<f32>; 3]) -> f32 {
+    m[0].dot(m[1].cross(m[2]))
+}
+
+/// Finds the point closest to all the planes, pulled slightly towards `mass_point` so the
+/// solution stays put when the planes are parallel.
+fn solve_qef(points: &[Vec3<f32>], normals: &[Vec3<f32>], mass_point: Vec3<f32>) -> Vec3<f32> {
+    const BIAS: f32 = 0.05;
+
+    let mut ata = [
+        Vec3::new(BIAS, 0.0, 0.0),
+        Vec3::new(0.0, BIAS, 0.0),
+        Vec3::new(0.0, 0.0, BIAS),
+    ];
+    let mut atb = mass_point * BIAS;
+    for (point, normal) in points.iter().zip(normals.iter()) {
+        for (row, n) in ata.iter_mut().zip(normal.iter()) {
+            *row += *normal * *n;
+        }
+        atb += *normal * normal.dot(*point);
+    }
+
+    // the matrix is symmetric, so its rows can be used as columns for Cramer's rule
+    let det = determinant(&ata);
+    if det.abs() < 1e-6 {
+        return mass_point;
+    }
+
+    Vec3::new(
+        determinant(&[atb, ata[1], ata[2]]),
+        determinant(&[ata[0], atb, ata[2]]),
+        determinant(&[ata[0], ata[1], atb]),
+    ) / det
+}
+
+/// Extracts a smooth mesh of the surface where `density` crosses zero.
+///
+/// `density` is positive inside the surface and negative outside. The voxels are treated as
+/// samples at their centres, so the mesh lines up with a cubic mesh of the same region. Each vertex
+/// carries the voxel of the densest corner of its cell as its data.
+///
+/// The cells between the last voxels of the region and the next voxels along are also visited, so
+/// neighbouring regions should overlap by one voxel for their meshes to join up.
+pub fn extract_surface_nets_mesh_custom<T, F>(
+    sampler: &mut dyn Sampler<T>,
+    region: &Region,
+    mesh: &mut Mesh<Vertex<T>>,
+    density: F,
+    placement: VertexPlacement,
+) -> Option<bool>
+where
+    T: Voxel,
+    F: Fn(T) -> f32,
+{
+    mesh.clear();
+
+    let lower = region.get_lower_corner();
+    let width = region.get_width() as usize;
+    let height = region.get_height() as usize;
+    let cell_index = |p: Vec3<i32>| {
+        let local = p - lower;
+        local.x as usize + local.y as usize * width + local.z as usize * width * height
+    };
+
+    let mut cell_vertices = vec![-1i32; region.get_volume() as usize];
+    let mut cell_masks = vec![0u8; region.get_volume() as usize];
+
+    let mut points = Vec::with_capacity(12);
+    let mut normals = Vec::with_capacity(12);
+
+    for position in region.iter() {
+        sampler.set_position(position.x, position.y, position.z);
+
+        let mut voxels = [sampler.get_voxel(); 8];
+        let mut densities = [0.0; 8];
+        let mut mask = 0u8;
+        for (i, corner) in CORNERS.iter().enumerate() {
+            voxels[i] = sampler.peek(corner.x as i32, corner.y as i32, corner.z as i32);
+            densities[i] = density(voxels[i]);
+            if densities[i] > 0.0 {
+                mask |= 1 << i;
+            }
+        }
+
+        let index = cell_index(position);
+        cell_masks[index] = mask;
+        if mask == 0 || mask == 0xff {
+            continue;
+        }
+
+        let mut gradients = [Vec3::zero(); 8];
+        for (gradient, corner) in gradients.iter_mut().zip(CORNERS.iter()) {
+            let corner = position + corner.map(|c| c as i32);
+            sampler.set_position(corner.x, corner.y, corner.z);
+            *gradient = get_gradient(sampler, &density);
+        }
+
+        points.clear();
+        normals.clear();
+        for i in 0..8 {
+            for bit in [1, 2, 4] {
+                let j = i | bit;
+                if i == j || (mask >> i) & 1 == (mask >> j) & 1 {
+                    continue;
+                }
+
+                let t = densities[i] / (densities[i] - densities[j]);
+                let point = CORNERS[i] + (CORNERS[j] - CORNERS[i]) * t;
+                points.push(point);
+                normals.push((gradients[i] + (gradients[j] - gradients[i]) * t).normalized());
+            }
+        }
+
+        let mass_point = points.iter().fold(Vec3::zero(), |a, p| a + *p) / points.len() as f32;
+        let local = match placement {
+            VertexPlacement::Average => mass_point,
+            VertexPlacement::Qef => {
+                let solution = solve_qef(&points, &normals, mass_point);
+                Vec3::partial_min(Vec3::partial_max(solution, Vec3::zero()), Vec3::one())
+            }
+        };
+
+        let gradient = trilinear(&gradients, local);
+        let normal = if gradient.magnitude_squared() > 0.0 {
+            -gradient.normalized()
+        } else {
+            Vec3::zero()
+        };
+
+        let densest = (0..8)
+            .max_by(|a, b| densities[*a].total_cmp(&densities[*b]))
+            .unwrap();
+
+        cell_vertices[index] = mesh.add_vertex(Vertex::new(
+            (position - lower).map(|c| c as f32) + local + 0.5,
+            normal,
+            voxels[densest],
+        )) as i32;
+    }
+
+    let upper = region.get_upper_corner();
+    for position in region.iter() {
+        let mask = cell_masks[cell_index(position)];
+
+        for axis in 0..3 {
+            let (b, c) = ((axis + 1) % 3, (axis + 2) % 3);
+            if position[axis] >= upper[axis] || position[b] <= lower[b] || position[c] <= lower[c] {
+                continue;
+            }
+
+            let inside = mask & 1 != 0;
+            if inside == ((mask >> (1 << axis)) & 1 != 0) {
+                continue;
+            }
+
+            let mut step_b = Vec3::zero();
+            step_b[b] = 1;
+            let mut step_c = Vec3::zero();
+            step_c[c] = 1;
+
+            let mut quad = [
+                cell_vertices[cell_index(position - step_b - step_c)],
+                cell_vertices[cell_index(position - step_c)],
+                cell_vertices[cell_index(position)],
+                cell_vertices[cell_index(position - step_b)],
+            ];
+            if !inside {
+                quad.reverse();
+            }
+
+            match mesh.face_arity() {
+                FaceArity::Three => {
+                    mesh.add_triangle(quad[0], quad[1], quad[2]);
+                    mesh.add_triangle(quad[0], quad[2], quad[3]);
+                }
+                FaceArity::Four => mesh.add_quad(quad[0], quad[1], quad[2], quad[3]),
+            }
+        }
+    }
+
+    mesh.set_offset(lower);
+
+    Some(true)
+}
+
+/// Extracts a surface nets mesh of the voxels denser than `threshold`.
+pub fn extract_surface_nets_mesh<T>(
+    sampler: &mut dyn Sampler<T>,
+    region: &Region,
+    threshold: f64,
+    face_arity: Option<FaceArity>,
+) -> Option<Mesh<Vertex<T>>>
+where
+    T: Density,
+{
+    let mut mesh = Mesh::new(face_arity.unwrap_or(FaceArity::Three));
+
+    extract_surface_nets_mesh_custom(
+        sampler,
+        region,
+        &mut mesh,
+        |v| (v.to_f64() - threshold) as f32,
+        VertexPlacement::Average,
+    )?;
+
+    Some(mesh)
+}
+
+/// Like `extract_surface_nets_mesh`, but places the vertices by dual contouring to keep sharp
+/// features.
+pub fn extract_dual_contouring_mesh<T>(
+    sampler: &mut dyn Sampler<T>,
+    region: &Region,
+    threshold: f64,
+    face_arity: Option<FaceArity>,
+) -> Option<Mesh<Vertex<T>>>
+where
+    T: Density,
+{
+    let mut mesh = Mesh::new(face_arity.unwrap_or(FaceArity::Three));
+
+    extract_surface_nets_mesh_custom(
+        sampler,
+        region,
+        &mut mesh,
+        |v| (v.to_f64() - threshold) as f32,
+        VertexPlacement::Qef,
+    )?;
+
+    Some(mesh)
+}
@@ -0,0 +1,98 @@
+use crate::cubic_surface_extractor::Face;
+use crate::voxel::Voxel;
+use vek::vec2::Vec2;
+
+/// Maps materials and face directions onto the tiles of a texture atlas.
+///
+/// The atlas is a grid of `columns` by `rows` equally sized tiles, numbered left to right and top
+/// to bottom starting at zero. It needs at least one of each.
+pub struct TextureAtlas<T>
+where
+    T: Voxel,
+{
+    columns: u32,
+    rows: u32,
+    default_tile: u32,
+    tiles: Vec<(T, Option<Face>, u32)>,
+}
+
+impl<T> TextureAtlas<T>
+where
+    T: Voxel,
+{
+    pub fn new(columns: u32, rows: u32) -> Self {
+        assert!(
+            columns > 0 && rows > 0,
+            "texture atlas needs at least one column and one row"
+        );
+
+        TextureAtlas {
+            columns,
+            rows,
+            default_tile: 0,
+            tiles: vec![],
+        }
+    }
+
+    /// Sets the tile used for materials which have no mapping.
+    pub fn set_default_tile(&mut self, tile: u32) {
+        self.default_tile = tile;
+    }
+
+    /// Uses `tile` for every face of `material` that has no face specific mapping.
+    pub fn set_tile(&mut self, material: T, tile: u32) {
+        self.insert(material, None, tile);
+    }
+
+    /// Uses `tile` for faces of `material` pointing in the direction of `face`.
+    pub fn set_face_tile(&mut self, material: T, face: Face, tile: u32) {
+        self.insert(material, Some(face), tile);
+    }
+
+    fn insert(&mut self, material: T, face: Option<Face>, tile: u32) {
+        self.tiles
+            .retain(|(m, f, _)| !(*m == material && *f == face));
+        self.tiles.push((material, face, tile));
+    }
+
+    pub fn get_tile(&self, material: T, face: Face) -> u32 {
+        let mut result = self.default_tile;
+        for (m, f, tile) in &self.tiles {
+            if *m == material {
+                match f {
+                    Some(f) if *f == face => return *tile,
+                    None => result = *tile,
+                    _ => {}
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Returns the lower and upper corners of `tile` in normalised texture coordinates.
+    pub fn get_tile_bounds(&self, tile: u32) -> (Vec2<f32>, Vec2<f32>) {
+        let size = Vec2::new(1.0 / self.columns as f32, 1.0 / self.rows as f32);
+        let lower = Vec2::new((tile % self.columns) as f32, (tile / self.columns) as f32) * size;
+
+        (lower, lower + size)
+    }
+
+    /// Wraps a repeating texture coordinate into `tile`.
+    ///
+    /// Whole numbers other than zero map onto the upper edge of the tile, so the corners of a quad
+    /// covering a single voxel face land on the corners of the tile.
+    pub fn get_atlas_uv(&self, tile: u32, uv: Vec2<f32>) -> Vec2<f32> {
+        let (lower, upper) = self.get_tile_bounds(tile);
+        let wrapped = uv.map(|c| {
+            let f = c - c.floor();
+            if f == 0.0 && c > 0.0 {
+                1.0
+            } else {
+                f
+            }
+        });
+
+        lower + wrapped * (upper - lower)
+    }
+}
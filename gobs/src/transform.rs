@@ -0,0 +1,149 @@
+use crate::raw_volume::RawVolume;
+use crate::region::Region;
+use crate::volume::Volume;
+use crate::voxel::Voxel;
+use vek::vec3::Vec3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    fn index(self) -> usize {
+        match self {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+        }
+    }
+}
+
+/// Builds a volume over `region` with the same border value as `volume`, reading each voxel from
+/// the position in `volume` that `source` gives.
+fn remap<T, F>(volume: &dyn Volume<T>, region: Region, source: F) -> RawVolume<T>
+where
+    T: Voxel,
+    F: Fn(Vec3<i32>) -> Vec3<i32>,
+{
+    let mut result = RawVolume::new(region);
+    result.set_border_value(volume.get_border_value());
+
+    for (p, voxel) in result.iter_mut() {
+        let from = source(p);
+        *voxel = volume.get_voxel_at(from.x, from.y, from.z);
+    }
+
+    result
+}
+
+/// Rotates the volume by `turns` quarter turns about `axis`, anticlockwise when looking back
+/// along the axis from its positive end. Negative turns rotate the other way.
+///
+/// The result keeps the lower corner of the original region, with its size along the two other
+/// axes swapped for odd numbers of turns.
+pub fn rotate_90<T>(volume: &dyn Volume<T>, axis: Axis, turns: i32) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    let region = volume.get_region();
+    let lower = region.get_lower_corner();
+    let size = region.get_upper_corner() - lower + 1;
+
+    let a = axis.index();
+    let (b, c) = ((a + 1) % 3, (a + 2) % 3);
+    let turns = turns.rem_euclid(4);
+
+    let mut new_size = size;
+    if turns % 2 == 1 {
+        new_size[b] = size[c];
+        new_size[c] = size[b];
+    }
+
+    remap(volume, Region::new(lower, lower + new_size - 1), |p| {
+        // undo the rotation one quarter turn at a time, where a quarter turn takes a position `l`
+        // in a volume of size `s` to `l[b] = s[c] - 1 - l[c]` and `l[c] = l[b]`
+        let mut local = p - lower;
+        let mut s = new_size;
+        for _ in 0..turns {
+            let (lb, lc) = (local[b], local[c]);
+            local[b] = lc;
+            local[c] = s[b] - 1 - lb;
+            let (sb, sc) = (s[b], s[c]);
+            s[b] = sc;
+            s[c] = sb;
+        }
+
+        lower + local
+    })
+}
+
+/// Mirrors the volume along `axis`, within its own region.
+pub fn mirror<T>(volume: &dyn Volume<T>, axis: Axis) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    let region = volume.get_region().clone();
+    let (lower, upper) = (region.get_lower_corner(), region.get_upper_corner());
+    let a = axis.index();
+
+    remap(volume, region, |mut p| {
+        p[a] = lower[a] + upper[a] - p[a];
+        p
+    })
+}
+
+/// Finds the smallest region holding every non-empty voxel, or `None` if they are all empty.
+pub fn content_region<T>(volume: &dyn Volume<T>) -> Option<Region>
+where
+    T: Voxel,
+{
+    volume
+        .get_region()
+        .iter()
+        .filter(|p| !volume.get_voxel_at(p.x, p.y, p.z).is_empty())
+        .fold(None, |bounds: Option<Region>, p| match bounds {
+            None => Some(Region::new(p, p)),
+            Some(r) => Some(Region::new(
+                Vec3::min(r.get_lower_corner(), p),
+                Vec3::max(r.get_upper_corner(), p),
+            )),
+        })
+}
+
+/// Copies the volume into a region fitted tightly around its non-empty voxels, which keep their
+/// positions. Returns `None` if every voxel is empty.
+pub fn crop_to_content<T>(volume: &dyn Volume<T>) -> Option<RawVolume<T>>
+where
+    T: Voxel,
+{
+    let region = content_region(volume)?;
+
+    Some(remap(volume, region, |p| p))
+}
+
+/// Copies the volume into a region grown by `amount` voxels on every side. The new voxels take the
+/// border value of `volume`.
+pub fn pad<T>(volume: &dyn Volume<T>, amount: i32) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    remap(volume, volume.get_region().grow(amount), |p| p)
+}
+
+/// Copies the volume so that its region starts at `lower` rather than its current lower corner.
+pub fn translate<T>(volume: &dyn Volume<T>, lower: Vec3<i32>) -> RawVolume<T>
+where
+    T: Voxel,
+{
+    let region = volume.get_region();
+    let offset = region.get_lower_corner() - lower;
+
+    remap(
+        volume,
+        Region::new(lower, region.get_upper_corner() - offset),
+        |p| p + offset,
+    )
+}
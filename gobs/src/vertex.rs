@@ -1,5 +1,6 @@
 use vek::vec3::Vec3;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vertex<T> {
     pub position: Vec3<f32>,
     pub normal: Vec3<f32>,
@@ -1,8 +1,12 @@
-use crate::region::Region;
+use crate::raw_volume::RawVolume;
+use crate::region::{Region, RegionIter};
 use crate::voxel::Voxel;
 use std::error::Error;
 use std::fmt;
 use std::fmt::Formatter;
+use std::iter::Filter;
+use std::marker::PhantomData;
+use vek::vec3::Vec3;
 
 #[derive(Debug)]
 pub struct PositionError {}
@@ -27,4 +31,93 @@ where
     fn calculate_size_in_bytes(&self) -> usize;
 
     fn get_border_value(&self) -> T;
+
+    /// Returns the positions written since the last call, for volumes that record modifications.
+    fn take_modified_positions(&mut self) -> Vec<Vec3<i32>> {
+        vec![]
+    }
+
+    /// Iterates over the position and value of every voxel in the volume's region.
+    fn iter(&self) -> VolumeIter<'_, T, Self>
+    where
+        Self: Sized,
+    {
+        VolumeIter::new(self, self.get_region())
+    }
+
+    /// Iterates over the voxels in `region`. Positions outside the volume yield the border value.
+    fn iter_region(&self, region: &Region) -> VolumeIter<'_, T, Self>
+    where
+        Self: Sized,
+    {
+        VolumeIter::new(self, region)
+    }
+
+    /// Iterates over the non-empty voxels in the volume's region.
+    fn iter_non_empty(&self) -> NonEmptyIter<'_, T, Self>
+    where
+        Self: Sized,
+    {
+        self.iter().filter(|(_, voxel)| !voxel.is_empty())
+    }
+
+    /// Copies the volume into a new `RawVolume`, converting every voxel and the border value.
+    fn map<U, F>(&self, f: F) -> RawVolume<U>
+    where
+        Self: Sized,
+        U: Voxel,
+        F: Fn(T) -> U,
+    {
+        let mut result = RawVolume::new(self.get_region().clone());
+        result.set_border_value(f(self.get_border_value()));
+        for ((_, voxel), converted) in self.iter().zip(result.data.iter_mut()) {
+            *converted = f(voxel);
+        }
+
+        result
+    }
+}
+
+/// Iterates over the voxels of a region of a volume, yielding each position and value.
+pub struct VolumeIter<'a, T, V>
+where
+    T: Voxel,
+    V: Volume<T> + ?Sized,
+{
+    volume: &'a V,
+    positions: RegionIter,
+    voxel_type: PhantomData<T>,
 }
+
+impl<'a, T, V> VolumeIter<'a, T, V>
+where
+    T: Voxel,
+    V: Volume<T> + ?Sized,
+{
+    pub fn new(volume: &'a V, region: &Region) -> Self {
+        VolumeIter {
+            volume,
+            positions: region.iter(),
+            voxel_type: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, V> Iterator for VolumeIter<'a, T, V>
+where
+    T: Voxel,
+    V: Volume<T> + ?Sized,
+{
+    type Item = (Vec3<i32>, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.positions.next()?;
+
+        Some((
+            position,
+            self.volume.get_voxel_at(position.x, position.y, position.z),
+        ))
+    }
+}
+
+pub type NonEmptyIter<'a, T, V> = Filter<VolumeIter<'a, T, V>, fn(&(Vec3<i32>, T)) -> bool>;
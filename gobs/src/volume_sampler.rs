@@ -0,0 +1,147 @@
+use crate::sampler::{get_neighbourhood_index, Sampler};
+use crate::volume::Volume;
+use crate::voxel::Voxel;
+use vek::vec3::Vec3;
+
+/// A sampler which works with any `Volume`.
+///
+/// The 3×3×3 block of voxels around the current position is cached, so peeking is a lookup and
+/// moving one step only reads the nine voxels of the newly uncovered face from the volume.
+pub struct VolumeSampler<'a, T, V>
+where
+    T: Voxel,
+    V: Volume<T> + ?Sized,
+{
+    volume: &'a V,
+    x_pos: i32,
+    y_pos: i32,
+    z_pos: i32,
+    cache: [T; 27],
+}
+
+impl<'a, T, V> VolumeSampler<'a, T, V>
+where
+    T: Voxel,
+    V: Volume<T> + ?Sized,
+{
+    pub fn new(volume: &'a V) -> Self {
+        let region = volume.get_region();
+        let mut result = VolumeSampler {
+            volume,
+            x_pos: region.lower_x,
+            y_pos: region.lower_y,
+            z_pos: region.lower_z,
+            cache: [Default::default(); 27],
+        };
+        result.fill_cache();
+
+        result
+    }
+
+    fn fill_cache(&mut self) {
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    self.cache[get_neighbourhood_index(dx, dy, dz)] =
+                        self.volume
+                            .get_voxel_at(self.x_pos + dx, self.y_pos + dy, self.z_pos + dz);
+                }
+            }
+        }
+    }
+
+    /// Shifts the cache one step along `axis` in direction `step` and reads the new face.
+    fn shift_cache(&mut self, axis: usize, step: i32) {
+        let offset = |a: i32, b: i32, c: i32| match axis {
+            0 => (a, b, c),
+            1 => (b, a, c),
+            _ => (b, c, a),
+        };
+
+        for b in -1..=1 {
+            for c in -1..=1 {
+                for a in [-step, 0] {
+                    let (dx, dy, dz) = offset(a, b, c);
+                    let (sx, sy, sz) = offset(a + step, b, c);
+                    self.cache[get_neighbourhood_index(dx, dy, dz)] =
+                        self.cache[get_neighbourhood_index(sx, sy, sz)];
+                }
+
+                let (dx, dy, dz) = offset(step, b, c);
+                self.cache[get_neighbourhood_index(dx, dy, dz)] =
+                    self.volume
+                        .get_voxel_at(self.x_pos + dx, self.y_pos + dy, self.z_pos + dz);
+            }
+        }
+    }
+}
+
+impl<'a, T, V> Sampler<T> for VolumeSampler<'a, T, V>
+where
+    T: Voxel,
+    V: Volume<T> + ?Sized,
+{
+    fn get_position(&self) -> Vec3<i32> {
+        Vec3 {
+            x: self.x_pos,
+            y: self.y_pos,
+            z: self.z_pos,
+        }
+    }
+
+    fn get_voxel(&self) -> T {
+        self.peek(0, 0, 0)
+    }
+
+    fn set_position(&mut self, x: i32, y: i32, z: i32) {
+        self.x_pos = x;
+        self.y_pos = y;
+        self.z_pos = z;
+        self.fill_cache();
+    }
+
+    fn move_positive_x(&mut self) {
+        self.x_pos += 1;
+        self.shift_cache(0, 1);
+    }
+
+    fn move_positive_y(&mut self) {
+        self.y_pos += 1;
+        self.shift_cache(1, 1);
+    }
+
+    fn move_positive_z(&mut self) {
+        self.z_pos += 1;
+        self.shift_cache(2, 1);
+    }
+
+    fn move_negative_x(&mut self) {
+        self.x_pos -= 1;
+        self.shift_cache(0, -1);
+    }
+
+    fn move_negative_y(&mut self) {
+        self.y_pos -= 1;
+        self.shift_cache(1, -1);
+    }
+
+    fn move_negative_z(&mut self) {
+        self.z_pos -= 1;
+        self.shift_cache(2, -1);
+    }
+
+    fn is_current_position_valid(&self) -> bool {
+        self.volume
+            .get_region()
+            .contains_point(self.x_pos, self.y_pos, self.z_pos)
+    }
+
+    #[inline]
+    fn peek(&self, dx: i32, dy: i32, dz: i32) -> T {
+        self.cache[get_neighbourhood_index(dx, dy, dz)]
+    }
+
+    fn fetch_3x3x3(&self) -> [T; 27] {
+        self.cache
+    }
+}
@@ -85,3 +85,47 @@ impl Voxel for f64 {
         self == 0.0
     }
 }
+
+/// A voxel type holding a scalar value, such as a density, which can be averaged and interpolated.
+pub trait Density: Voxel {
+    fn to_f64(self) -> f64;
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_density {
+    ($($t:ty),*) => {
+        $(
+            impl Density for $t {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn from_f64(value: f64) -> Self {
+                    value.round() as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_density!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl Density for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl Density for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
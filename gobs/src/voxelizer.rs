@@ -0,0 +1,315 @@
+use crate::region::Region;
+use crate::volume::Volume;
+use crate::voxel::Voxel;
+use std::io::{self, BufRead};
+use vek::vec3::Vec3;
+
+/// A triangle to be voxelized, with the material it should write into the volume.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Triangle<T>
+where
+    T: Voxel,
+{
+    pub vertices: [Vec3<f32>; 3],
+    pub material: T,
+}
+
+impl<T> Triangle<T>
+where
+    T: Voxel,
+{
+    pub fn new(v0: Vec3<f32>, v1: Vec3<f32>, v2: Vec3<f32>, material: T) -> Self {
+        Triangle {
+            vertices: [v0, v1, v2],
+            material,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VoxelizeMode {
+    /// Only fill the voxels the triangles pass through.
+    Surface,
+    /// Also fill the voxels inside the mesh, which should then be closed.
+    Solid,
+}
+
+/// Scales and translates the triangles uniformly so their bounds fit inside `region`.
+///
+/// The mesh is centred in the region along any axis where it does not fill it.
+pub fn fit_to_region<T>(triangles: &mut [Triangle<T>], region: &Region)
+where
+    T: Voxel,
+{
+    let mut points = triangles.iter().flat_map(|t| t.vertices.iter().copied());
+    let first = match points.next() {
+        Some(p) => p,
+        None => return,
+    };
+    let (lower, upper) = points.fold((first, first), |(lower, upper), p| {
+        (Vec3::partial_min(lower, p), Vec3::partial_max(upper, p))
+    });
+
+    let target_lower = region.get_lower_corner().map(|c| c as f32);
+    let target_size = Vec3::new(
+        region.get_width() as f32,
+        region.get_height() as f32,
+        region.get_depth() as f32,
+    );
+    let size = upper - lower;
+    let scale = (0..3)
+        .filter(|axis| size[*axis] > 0.0)
+        .map(|axis| target_size[axis] / size[axis])
+        .fold(f32::INFINITY, f32::min);
+    let scale = if scale.is_finite() { scale } else { 1.0 };
+    let offset = target_lower + (target_size - size * scale) / 2.0;
+
+    for triangle in triangles.iter_mut() {
+        for vertex in triangle.vertices.iter_mut() {
+            *vertex = (*vertex - lower) * scale + offset;
+        }
+    }
+}
+
+fn project(points: &[Vec3<f32>; 3], axis: Vec3<f32>) -> (f32, f32) {
+    let d0 = points[0].dot(axis);
+    let d1 = points[1].dot(axis);
+    let d2 = points[2].dot(axis);
+
+    (d0.min(d1).min(d2), d0.max(d1).max(d2))
+}
+
+/// Tests if a triangle overlaps the box centred at `centre`, using the separating axis theorem.
+fn triangle_overlaps_box(triangle: &[Vec3<f32>; 3], centre: Vec3<f32>, half: f32) -> bool {
+    let points = [
+        triangle[0] - centre,
+        triangle[1] - centre,
+        triangle[2] - centre,
+    ];
+    let edges = [
+        points[1] - points[0],
+        points[2] - points[1],
+        points[0] - points[2],
+    ];
+    let box_axes = [Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()];
+
+    let separated = |axis: Vec3<f32>| {
+        let radius = half * (axis.x.abs() + axis.y.abs() + axis.z.abs());
+        let (min, max) = project(&points, axis);
+        min > radius || max < -radius
+    };
+
+    // the box is half open along its own axes, so a point on the boundary between two voxels
+    // only belongs to the upper one
+    for box_axis in box_axes.iter() {
+        let (min, max) = project(&points, *box_axis);
+        if min >= half || max < -half {
+            return false;
+        }
+    }
+
+    if separated(edges[0].cross(edges[1])) {
+        return false;
+    }
+
+    for edge in edges.iter() {
+        for box_axis in box_axes.iter() {
+            if separated(edge.cross(*box_axis)) {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+fn get_bounds(triangle: &[Vec3<f32>; 3], region: &Region) -> Option<Region> {
+    let lower = Vec3::partial_min(Vec3::partial_min(triangle[0], triangle[1]), triangle[2]);
+    let upper = Vec3::partial_max(Vec3::partial_max(triangle[0], triangle[1]), triangle[2]);
+
+    Region::new(
+        lower.map(|c: f32| c.floor() as i32),
+        upper.map(|c: f32| c.floor() as i32),
+    )
+    .intersect(region)
+}
+
+/// Tests if none of the triangle's coordinates are NaN or infinite.
+fn has_finite_vertices<T>(triangle: &Triangle<T>) -> bool
+where
+    T: Voxel,
+{
+    triangle
+        .vertices
+        .iter()
+        .all(|v| v.x.is_finite() && v.y.is_finite() && v.z.is_finite())
+}
+
+/// Writes the material of each triangle into the voxels it passes through.
+fn voxelize_surface<T>(triangles: &[Triangle<T>], volume: &mut dyn Volume<T>, region: &Region)
+where
+    T: Voxel,
+{
+    for triangle in triangles.iter().filter(|t| has_finite_vertices(t)) {
+        let bounds = match get_bounds(&triangle.vertices, region) {
+            Some(bounds) => bounds,
+            None => continue,
+        };
+
+        for position in bounds.iter() {
+            let centre = position.map(|c| c as f32 + 0.5);
+            if triangle_overlaps_box(&triangle.vertices, centre, 0.5) {
+                let _ = volume.set_voxel_at(position.x, position.y, position.z, triangle.material);
+            }
+        }
+    }
+}
+
+/// Finds where a ray travelling along +z through `(x, y)` crosses the triangle.
+fn intersect_z_ray(triangle: &[Vec3<f32>; 3], x: f32, y: f32) -> Option<f32> {
+    let [a, b, c] = *triangle;
+    let det = (b.y - c.y) * (a.x - c.x) + (c.x - b.x) * (a.y - c.y);
+    if det == 0.0 {
+        return None;
+    }
+
+    let l0 = ((b.y - c.y) * (x - c.x) + (c.x - b.x) * (y - c.y)) / det;
+    let l1 = ((c.y - a.y) * (x - c.x) + (a.x - c.x) * (y - c.y)) / det;
+    let l2 = 1.0 - l0 - l1;
+
+    if l0 >= 0.0 && l1 >= 0.0 && l2 >= 0.0 {
+        Some(l0 * a.z + l1 * b.z + l2 * c.z)
+    } else {
+        None
+    }
+}
+
+/// Fills the voxels between each pair of crossings of a ray through every voxel column.
+fn voxelize_interior<T>(triangles: &[Triangle<T>], volume: &mut dyn Volume<T>, region: &Region)
+where
+    T: Voxel,
+{
+    let mut crossings: Vec<(f32, T)> = vec![];
+
+    for y in region.lower_y..=region.upper_y {
+        for x in region.lower_x..=region.upper_x {
+            // nudge the ray off the centre so it does not run exactly along the edges of meshes
+            // built on the voxel grid, where it would hit both neighbouring triangles
+            let (rx, ry) = (x as f32 + 0.500_31, y as f32 + 0.500_17);
+
+            crossings.clear();
+            crossings.extend(
+                triangles
+                    .iter()
+                    .filter(|triangle| has_finite_vertices(triangle))
+                    .filter_map(|triangle| {
+                        intersect_z_ray(&triangle.vertices, rx, ry).map(|z| (z, triangle.material))
+                    }),
+            );
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            for pair in crossings.chunks_exact(2) {
+                let (enter, material) = pair[0];
+                let exit = pair[1].0;
+
+                let lower_z = ((enter - 0.5).ceil() as i32).max(region.lower_z);
+                let upper_z = ((exit - 0.5).floor() as i32).min(region.upper_z);
+                for z in lower_z..=upper_z {
+                    let _ = volume.set_voxel_at(x, y, z, material);
+                }
+            }
+        }
+    }
+}
+
+/// Fills the voxels of `region` covered by the triangles.
+///
+/// Triangle positions are in voxel coordinates, where the voxel at `(x, y, z)` covers the unit
+/// cube from `(x, y, z)` to `(x + 1, y + 1, z + 1)`. Use `fit_to_region` to place a mesh made in
+/// other units. In solid mode a voxel counts as inside if its centre is, which relies on the mesh
+/// being closed. Triangles with NaN or infinite coordinates are skipped.
+pub fn voxelize<T>(
+    triangles: &[Triangle<T>],
+    volume: &mut dyn Volume<T>,
+    region: &Region,
+    mode: VoxelizeMode,
+) where
+    T: Voxel,
+{
+    if mode == VoxelizeMode::Solid {
+        voxelize_interior(triangles, volume, region);
+    }
+
+    voxelize_surface(triangles, volume, region);
+}
+
+fn parse_index(token: &str, vertex_count: usize) -> io::Result<usize> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid face index");
+    let index: i64 = token
+        .split('/')
+        .next()
+        .and_then(|i| i.parse().ok())
+        .ok_or_else(invalid)?;
+
+    let index = if index < 0 {
+        vertex_count as i64 + index
+    } else {
+        index - 1
+    };
+
+    if index >= 0 && (index as usize) < vertex_count {
+        Ok(index as usize)
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Reads the triangles of a Wavefront OBJ file.
+///
+/// Polygons are split into fans of triangles. `material` is called with the name given by the
+/// most recent `usemtl` line, if any, to choose the material of each face.
+pub fn load_obj<T, F>(reader: &mut dyn BufRead, material: F) -> io::Result<Vec<Triangle<T>>>
+where
+    T: Voxel,
+    F: Fn(Option<&str>) -> T,
+{
+    let mut positions: Vec<Vec3<f32>> = vec![];
+    let mut triangles = vec![];
+    let mut current_material = material(None);
+
+    for line in reader.lines() {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("v") => {
+                let coordinates: Vec<f32> = tokens
+                    .take(3)
+                    .map(|t| t.parse())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid vertex"))?;
+                if coordinates.len() != 3 {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid vertex"));
+                }
+                positions.push(Vec3::new(coordinates[0], coordinates[1], coordinates[2]));
+            }
+            Some("f") => {
+                let indices = tokens
+                    .map(|t| parse_index(t, positions.len()))
+                    .collect::<io::Result<Vec<usize>>>()?;
+                for i in 1..indices.len().saturating_sub(1) {
+                    triangles.push(Triangle::new(
+                        positions[indices[0]],
+                        positions[indices[i]],
+                        positions[indices[i + 1]],
+                        current_material,
+                    ));
+                }
+            }
+            Some("usemtl") => current_material = material(tokens.next()),
+            _ => {}
+        }
+    }
+
+    Ok(triangles)
+}
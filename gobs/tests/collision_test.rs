@@ -0,0 +1,97 @@
+use gobs::collision::{extract_boxes, extract_collision_mesh};
+use gobs::cubic_surface_extractor::extract_cubic_mesh;
+use gobs::raw_volume::RawVolume;
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+fn fill(volume: &mut RawVolume<u8>, region: &Region, value: u8) {
+    for p in region.iter() {
+        volume.set_voxel_at(p.x, p.y, p.z, value).unwrap();
+    }
+}
+
+#[test]
+fn solid_block_is_one_box() {
+    let mut volume = RawVolume::new(Region::new(Vec3::zero(), Vec3::broadcast(7)));
+    let block = Region::new(Vec3::new(1, 2, 3), Vec3::new(5, 4, 6));
+    fill(&mut volume, &block, 1);
+    volume.set_voxel_at(3, 3, 4, 2).unwrap();
+
+    let boxes = extract_boxes(&volume, volume.get_region());
+    assert_eq!(boxes.len(), 1);
+    assert_eq!(boxes[0].get_lower_corner(), block.get_lower_corner());
+    assert_eq!(boxes[0].get_upper_corner(), block.get_upper_corner());
+}
+
+#[test]
+fn boxes_cover_solid_voxels_once() {
+    let mut volume = RawVolume::new(Region::new(Vec3::zero(), Vec3::broadcast(7)));
+    fill(
+        &mut volume,
+        &Region::new(Vec3::zero(), Vec3::new(7, 0, 7)),
+        1,
+    );
+    fill(
+        &mut volume,
+        &Region::new(Vec3::new(2, 1, 2), Vec3::new(3, 5, 3)),
+        1,
+    );
+    volume.set_voxel_at(6, 6, 6, 1).unwrap();
+
+    let boxes = extract_boxes(&volume, volume.get_region());
+    assert_eq!(boxes.len(), 3);
+
+    let mut covered: RawVolume<u8> = RawVolume::new(volume.get_region().clone());
+    for b in boxes.iter() {
+        for p in b.iter() {
+            assert_eq!(covered.get_voxel_at(p.x, p.y, p.z), 0);
+            covered.set_voxel_at(p.x, p.y, p.z, 1).unwrap();
+        }
+    }
+    assert_eq!(covered.get_data(), volume.get_data());
+
+    let clipped = extract_boxes(
+        &volume,
+        &Region::new(Vec3::broadcast(5), Vec3::broadcast(9)),
+    );
+    assert_eq!(clipped.len(), 1);
+}
+
+#[test]
+fn collision_mesh_ignores_materials() {
+    let mut volume = RawVolume::new(Region::new(Vec3::zero(), Vec3::broadcast(5)));
+    fill(
+        &mut volume,
+        &Region::new(Vec3::broadcast(1), Vec3::broadcast(4)),
+        1,
+    );
+    fill(
+        &mut volume,
+        &Region::new(Vec3::broadcast(1), Vec3::new(2, 4, 4)),
+        2,
+    );
+
+    let mut single: RawVolume<u8> = RawVolume::new(volume.get_region().clone());
+    fill(
+        &mut single,
+        &Region::new(Vec3::broadcast(1), Vec3::broadcast(4)),
+        1,
+    );
+
+    let mut sampler = RawVolumeSampler::new(&volume);
+    let render = extract_cubic_mesh(&mut sampler, volume.get_region(), None, None).unwrap();
+    let collision = extract_collision_mesh(&mut sampler, volume.get_region()).unwrap();
+    let single_render = extract_cubic_mesh(
+        &mut RawVolumeSampler::new(&single),
+        volume.get_region(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(collision.indices().len(), single_render.indices().len());
+    assert!(render.indices().len() > collision.indices().len());
+    assert!(collision.vertices().iter().all(|v| v.data == 0));
+}
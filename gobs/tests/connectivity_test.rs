@@ -0,0 +1,68 @@
+use gobs::connectivity::{label_components, Connectivity};
+use gobs::raw_volume::RawVolume;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+fn create_volume() -> RawVolume<u8> {
+    let mut volume = RawVolume::new(Region::new(Vec3::zero(), Vec3::broadcast(7)));
+
+    // a pillar standing on the ground
+    for y in 0..4 {
+        volume.set_voxel_at(1, y, 1, 1).unwrap();
+    }
+
+    // a floating block and a voxel touching it only along an edge
+    for p in Region::new(Vec3::new(4, 4, 4), Vec3::new(5, 5, 4)).iter() {
+        volume.set_voxel_at(p.x, p.y, p.z, 2).unwrap();
+    }
+    volume.set_voxel_at(6, 6, 4, 3).unwrap();
+
+    volume
+}
+
+#[test]
+fn connectivity_offsets() {
+    assert_eq!(Connectivity::Six.offsets().len(), 6);
+    assert_eq!(Connectivity::Eighteen.offsets().len(), 18);
+    assert_eq!(Connectivity::TwentySix.offsets().len(), 26);
+}
+
+#[test]
+fn label_and_find_islands() {
+    let volume = create_volume();
+
+    let six = label_components(&volume, volume.get_region(), Connectivity::Six);
+    assert_eq!(six.components.len(), 3);
+
+    let labels = label_components(&volume, volume.get_region(), Connectivity::Eighteen);
+    assert_eq!(labels.components.len(), 2);
+    assert_eq!(labels.get_label(1, 2, 1), 1);
+    assert_eq!(labels.get_label(0, 0, 0), 0);
+    assert_eq!(labels.get_label(6, 6, 4), labels.get_label(4, 4, 4));
+
+    let block = labels.get_component(2).unwrap();
+    assert_eq!(block.voxel_count, 5);
+    assert_eq!(block.region.get_lower_corner(), Vec3::new(4, 4, 4));
+    assert_eq!(block.region.get_upper_corner(), Vec3::new(6, 6, 4));
+
+    let islands = labels.find_islands(|p| p.y == 0);
+    assert_eq!(islands.len(), 1);
+    assert_eq!(islands[0].label, 2);
+}
+
+#[test]
+fn extract_island() {
+    let volume = create_volume();
+    let labels = label_components(&volume, volume.get_region(), Connectivity::TwentySix);
+
+    let island = labels.extract_component(&volume, 2).unwrap();
+    assert_eq!(island.get_region().get_width(), 3);
+    assert_eq!(island.get_voxel_at(5, 5, 4), 2);
+    assert_eq!(island.get_voxel_at(6, 6, 4), 3);
+    assert_eq!(island.get_voxel_at(6, 4, 4), 0);
+    assert_eq!(island.iter_non_empty().count(), 5);
+
+    assert!(labels.extract_component(&volume, 0).is_none());
+    assert!(labels.extract_component(&volume, 3).is_none());
+}
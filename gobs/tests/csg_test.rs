@@ -0,0 +1,92 @@
+use gobs::csg::{combine, combine_in_place, ConflictPolicy, CsgOperation};
+use gobs::raw_volume::RawVolume;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+fn create_volumes() -> (RawVolume<u8>, RawVolume<u8>) {
+    let mut a = RawVolume::new(Region::cubic(7));
+    for p in Region::new(Vec3::broadcast(2), Vec3::broadcast(5)).iter() {
+        a.set_voxel_at(p.x, p.y, p.z, 1).unwrap();
+    }
+
+    let mut b = RawVolume::new(Region::cubic(3));
+    for (_, voxel) in b.iter_mut() {
+        *voxel = 2;
+    }
+
+    (a, b)
+}
+
+fn count(volume: &RawVolume<u8>) -> usize {
+    volume.iter_non_empty().count()
+}
+
+#[test]
+fn combine_into_new_volume() {
+    let (a, b) = create_volumes();
+    let offset = Vec3::broadcast(4);
+
+    let union = combine(&a, &b, offset, CsgOperation::Union, &ConflictPolicy::KeepA);
+    assert_eq!(count(&union), 64 + 64 - 8);
+    assert_eq!(union.get_voxel_at(4, 4, 4), 1);
+    assert_eq!(union.get_voxel_at(7, 7, 7), 2);
+
+    let union = combine(&a, &b, offset, CsgOperation::Union, &ConflictPolicy::KeepB);
+    assert_eq!(union.get_voxel_at(4, 4, 4), 2);
+
+    let policy = ConflictPolicy::Custom(Box::new(|a: u8, b: u8| a + b));
+    let intersection = combine(&a, &b, offset, CsgOperation::Intersection, &policy);
+    assert_eq!(count(&intersection), 8);
+    assert_eq!(intersection.get_voxel_at(5, 5, 5), 3);
+
+    let difference = combine(&a, &b, offset, CsgOperation::Difference, &policy);
+    assert_eq!(count(&difference), 64 - 8);
+    assert_eq!(difference.get_voxel_at(4, 4, 4), 0);
+    assert_eq!(difference.get_voxel_at(3, 4, 4), 1);
+}
+
+#[test]
+fn combine_grows_to_cover_both_volumes() {
+    let (a, b) = create_volumes();
+
+    let union = combine(
+        &a,
+        &b,
+        Vec3::new(-2, 6, 0),
+        CsgOperation::Union,
+        &ConflictPolicy::KeepA,
+    );
+    assert_eq!(union.get_region().get_lower_corner(), Vec3::new(-2, 0, 0));
+    assert_eq!(union.get_region().get_upper_corner(), Vec3::new(7, 9, 7));
+    assert_eq!(count(&union), 128);
+}
+
+#[test]
+fn combine_in_place_matches_new_volume() {
+    let (a, b) = create_volumes();
+
+    for operation in [
+        CsgOperation::Union,
+        CsgOperation::Intersection,
+        CsgOperation::Difference,
+    ] {
+        for offset in [Vec3::broadcast(4), Vec3::broadcast(6), Vec3::broadcast(20)] {
+            let expected = combine(&a, &b, offset, operation, &ConflictPolicy::KeepB);
+
+            let mut in_place = create_volumes().0;
+            combine_in_place(&mut in_place, &b, offset, operation, &ConflictPolicy::KeepB).unwrap();
+
+            for p in in_place.get_region().iter() {
+                assert_eq!(
+                    in_place.get_voxel_at(p.x, p.y, p.z),
+                    expected.get_voxel_at(p.x, p.y, p.z),
+                    "{:?} at {:?} with offset {:?}",
+                    operation,
+                    p,
+                    offset
+                );
+            }
+        }
+    }
+}
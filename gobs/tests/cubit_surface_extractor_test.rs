@@ -1,8 +1,8 @@
-use gobs_core::cubic_surface_extractor::extract_cubic_mesh;
-use gobs_core::raw_volume::RawVolume;
-use gobs_core::raw_volume_sampler::RawVolumeSampler;
-use gobs_core::region::Region;
-use gobs_core::volume::Volume;
+use gobs::cubic_surface_extractor::extract_cubic_mesh;
+use gobs::raw_volume::RawVolume;
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::region::Region;
+use gobs::volume::Volume;
 
 #[test]
 fn basic_case() {
@@ -11,10 +11,10 @@ fn basic_case() {
     volume.set_voxel_at(8, 8, 8, 1).unwrap();
 
     let mut sampler = RawVolumeSampler::new(&volume);
-    let mesh = extract_cubic_mesh(&mut sampler, &Region::cubic(16)).unwrap();
+    let mesh = extract_cubic_mesh(&mut sampler, &Region::cubic(16), None, None).unwrap();
 
-    assert_eq!(mesh.vertices.len(), 8);
-    assert_eq!(mesh.indices.len(), 36);
+    assert_eq!(mesh.vertices().len(), 8);
+    assert_eq!(mesh.indices().len(), 36);
 }
 
 #[test]
@@ -25,8 +25,8 @@ fn combine_two_voxels_case() {
     volume.set_voxel_at(9, 8, 8, 1).unwrap();
 
     let mut sampler = RawVolumeSampler::new(&volume);
-    let mesh = extract_cubic_mesh(&mut sampler, &Region::cubic(16)).unwrap();
+    let mesh = extract_cubic_mesh(&mut sampler, &Region::cubic(16), None, None).unwrap();
 
-    assert_eq!(mesh.vertices.len(), 8);
-    assert_eq!(mesh.indices.len(), 36);
+    assert_eq!(mesh.vertices().len(), 8);
+    assert_eq!(mesh.indices().len(), 36);
 }
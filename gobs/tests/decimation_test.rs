@@ -0,0 +1,166 @@
+use gobs::cubic_surface_extractor::{
+    extract_cubic_mesh, extract_material_agnostic_cubic_mesh, CubicVertex, Face,
+};
+use gobs::decimation::remove_t_junctions;
+use gobs::mesh::{FaceArity, Mesh};
+use gobs::raw_volume::RawVolume;
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+fn create_volume() -> RawVolume<u8> {
+    // a 2x2x2 block with a single voxel of a different material next to it
+    let mut volume = RawVolume::new(Region::new(Vec3::zero(), Vec3::new(3, 3, 3)));
+    for z in 1..=2 {
+        for y in 1..=2 {
+            for x in 1..=2 {
+                volume.set_voxel_at(x, y, z, 1).unwrap();
+            }
+        }
+    }
+    volume.set_voxel_at(3, 1, 1, 2).unwrap();
+
+    volume
+}
+
+fn position(mesh: &Mesh<CubicVertex<u8>>, index: i32) -> Vec3<f32> {
+    mesh.vertices()[index as usize].decode().map(|c| c as f32)
+}
+
+fn area(mesh: &Mesh<CubicVertex<u8>>, arity: usize) -> f32 {
+    mesh.indices()
+        .chunks(arity)
+        .map(|face| {
+            (2..arity)
+                .map(|i| {
+                    let a = position(mesh, face[0]);
+                    (position(mesh, face[i - 1]) - a)
+                        .cross(position(mesh, face[i]) - a)
+                        .magnitude()
+                        / 2.0
+                })
+                .sum::<f32>()
+        })
+        .sum()
+}
+
+fn count_t_junctions(mesh: &Mesh<CubicVertex<u8>>) -> usize {
+    let positions: Vec<Vec3<f32>> = (0..mesh.vertices().len())
+        .map(|i| position(mesh, i as i32))
+        .collect();
+
+    let mut count = 0;
+    for triangle in mesh.indices().chunks(3) {
+        for i in 0..3 {
+            let a = position(mesh, triangle[i]);
+            let b = position(mesh, triangle[(i + 1) % 3]);
+            count += positions
+                .iter()
+                .filter(|p| {
+                    let t = (**p - a).dot(b - a) / (b - a).magnitude_squared();
+                    t > 0.0 && t < 1.0 && (a + (b - a) * t - **p).magnitude() < 0.0001
+                })
+                .count();
+        }
+    }
+
+    count
+}
+
+#[test]
+fn t_junctions_are_removed() {
+    let volume = create_volume();
+    let mesh = extract_cubic_mesh(
+        &mut RawVolumeSampler::new(&volume),
+        volume.get_region(),
+        Some(FaceArity::Four),
+        None,
+    )
+    .unwrap();
+
+    let triangles = extract_cubic_mesh(
+        &mut RawVolumeSampler::new(&volume),
+        volume.get_region(),
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(count_t_junctions(&triangles) > 0);
+
+    let repaired = remove_t_junctions(&mesh);
+
+    assert_eq!(repaired.face_arity(), FaceArity::Three);
+    assert_eq!(count_t_junctions(&repaired), 0);
+    assert!((area(&repaired, 3) - area(&mesh, 4)).abs() < 0.0001);
+    assert!(repaired.indices().len() / 3 > mesh.indices().len() / 4 * 2);
+}
+
+#[test]
+fn t_junctions_on_several_edges() {
+    // the merged face underneath the lower row has split vertices on more than one of its edges
+    let mut volume: RawVolume<u8> = RawVolume::new(Region::new(Vec3::zero(), Vec3::new(3, 3, 3)));
+    volume.set_voxel_at(1, 1, 1, 1).unwrap();
+    volume.set_voxel_at(2, 1, 1, 1).unwrap();
+    volume.set_voxel_at(1, 1, 2, 2).unwrap();
+    volume.set_voxel_at(2, 1, 2, 3).unwrap();
+
+    let mesh = extract_cubic_mesh(
+        &mut RawVolumeSampler::new(&volume),
+        volume.get_region(),
+        Some(FaceArity::Four),
+        None,
+    )
+    .unwrap();
+    let repaired = remove_t_junctions(&mesh);
+
+    assert_eq!(count_t_junctions(&repaired), 0);
+    assert!((area(&repaired, 3) - area(&mesh, 4)).abs() < 0.0001);
+}
+
+#[test]
+fn material_agnostic_merge() {
+    let mut volume: RawVolume<u8> = RawVolume::new(Region::new(Vec3::zero(), Vec3::new(4, 2, 2)));
+    for x in 0..4 {
+        volume.set_voxel_at(x, 1, 1, 1 + (x % 2) as u8).unwrap();
+    }
+
+    let mut sampler = RawVolumeSampler::new(&volume);
+    let mesh = extract_cubic_mesh(
+        &mut sampler,
+        volume.get_region(),
+        Some(FaceArity::Four),
+        None,
+    )
+    .unwrap();
+    let (merged, materials) = extract_material_agnostic_cubic_mesh(
+        &mut sampler,
+        volume.get_region(),
+        Some(FaceArity::Four),
+    )
+    .unwrap();
+
+    assert_eq!(merged.indices().len(), 6 * 4);
+    assert!(mesh.indices().len() > merged.indices().len());
+    assert_eq!(materials.len(), 6);
+
+    let top = materials
+        .iter()
+        .find(|m| m.face == Face::PositiveY)
+        .unwrap();
+    assert_eq!((top.width, top.height), (4, 1));
+    assert_eq!(top.materials, vec![1, 2, 1, 2]);
+
+    // the far end of the row faces the other way, so it runs backwards in texture space
+    let back = materials
+        .iter()
+        .find(|m| m.face == Face::NegativeZ)
+        .unwrap();
+    assert_eq!(back.materials, vec![2, 1, 2, 1]);
+
+    let end = materials
+        .iter()
+        .find(|m| m.face == Face::PositiveX)
+        .unwrap();
+    assert_eq!(end.materials, vec![2]);
+}
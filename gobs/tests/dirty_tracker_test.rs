@@ -0,0 +1,62 @@
+use gobs::dirty_tracker::{extract_dirty_cubic_meshes, DirtyTracker};
+use gobs::raw_volume::RawVolume;
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+#[test]
+fn interior_edit_dirties_one_chunk() {
+    let mut tracker = DirtyTracker::new(16);
+    tracker.mark_modified(8, 8, 8);
+
+    assert_eq!(tracker.take_dirty_chunks(), vec![Vec3::new(0, 0, 0)]);
+    assert!(tracker.is_clean());
+}
+
+#[test]
+fn border_edit_dirties_neighbours() {
+    let mut tracker = DirtyTracker::new(16);
+    tracker.mark_modified(15, 8, 16);
+
+    assert_eq!(
+        tracker.take_dirty_chunks(),
+        vec![Vec3::new(0, 0, 1), Vec3::new(1, 0, 1)]
+    );
+}
+
+#[test]
+fn lower_border_edit_dirties_one_chunk() {
+    // the chunk below never samples the edited voxel, as the extractor only looks at the
+    // neighbours on the negative side
+    let mut tracker = DirtyTracker::new(16);
+    tracker.mark_modified(16, 0, -16);
+
+    assert_eq!(tracker.take_dirty_chunks(), vec![Vec3::new(1, 0, -1)]);
+}
+
+#[test]
+fn remesh_recorded_modifications() {
+    let mut volume: RawVolume<u8> = RawVolume::new(Region::sized(31, 31, 31));
+    volume.set_voxel_at(1, 1, 1, 1).unwrap();
+    volume.set_track_modifications(true);
+    volume.set_voxel_at(20, 4, 4, 1).unwrap();
+
+    let mut tracker = DirtyTracker::new(16);
+    tracker.mark_volume_modifications(&mut volume);
+    assert!(volume.take_modified_positions().is_empty());
+
+    let bounds = volume.get_region().clone();
+    let meshes = extract_dirty_cubic_meshes(
+        &mut tracker,
+        &mut RawVolumeSampler::new(&volume),
+        &bounds,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(meshes.len(), 1);
+    assert_eq!(meshes[0].0, Vec3::new(1, 0, 0));
+    assert_eq!(meshes[0].1.vertices().len(), 8);
+}
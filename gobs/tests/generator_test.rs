@@ -0,0 +1,100 @@
+use gobs::generator::{generate, generate_heightmap, generate_sdf, Noise, NoiseKind};
+use gobs::raw_volume::RawVolume;
+use gobs::region::Region;
+use gobs::sdf::{Cuboid, Sdf, Sphere};
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+#[test]
+fn noise_is_deterministic() {
+    let a = Noise::new(42);
+    let b = Noise::new(42);
+    let c = Noise::new(43);
+
+    for kind in [NoiseKind::Value, NoiseKind::Perlin, NoiseKind::Simplex].iter() {
+        let mut differs = false;
+        for i in 0..100 {
+            let position = Vec3::new(i as f64 * 0.37, i as f64 * 0.11, i as f64 * -0.53);
+            let value = a.sample(*kind, position);
+
+            assert!((-1.0..=1.0).contains(&value));
+            assert_eq!(value, b.sample(*kind, position));
+            differs |= value != c.sample(*kind, position);
+        }
+        assert!(differs);
+    }
+}
+
+#[test]
+fn chunks_match_whole_volume() {
+    let noise = Noise::new(7);
+    let density = |p: Vec3<i32>| {
+        let value = noise.fractal(NoiseKind::Simplex, p.map(|c| c as f64 * 0.1), 3);
+        if value > 0.0 {
+            1u8
+        } else {
+            0
+        }
+    };
+
+    let region = Region::new(Vec3::zero(), Vec3::broadcast(15));
+    let mut whole = RawVolume::new(region.clone());
+    generate(&mut whole, &region, density);
+
+    let mut chunked = RawVolume::new(region.clone());
+    for z in 0..2 {
+        for y in 0..2 {
+            for x in 0..2 {
+                let lower = Vec3::new(x, y, z) * 8;
+                generate(&mut chunked, &Region::new(lower, lower + 7), density);
+            }
+        }
+    }
+
+    assert_eq!(whole.get_data(), chunked.get_data());
+    assert!(whole.iter_non_empty().count() > 0);
+}
+
+#[test]
+fn heightmap_fills_below_ground() {
+    let region = Region::new(Vec3::zero(), Vec3::broadcast(7));
+    let mut volume = RawVolume::new(region.clone());
+    volume.set_voxel_at(0, 7, 0, 9u8).unwrap();
+
+    generate_heightmap(
+        &mut volume,
+        &region,
+        |x, _| x as f64,
+        |_, depth| if depth <= 1.0 { 1 } else { 2 },
+    );
+
+    assert_eq!(volume.get_voxel_at(0, 0, 0), 0);
+    assert_eq!(volume.get_voxel_at(0, 7, 0), 0);
+    assert_eq!(volume.get_voxel_at(3, 2, 5), 1);
+    assert_eq!(volume.get_voxel_at(3, 0, 5), 2);
+    assert_eq!(volume.get_voxel_at(3, 3, 5), 0);
+}
+
+#[test]
+fn sdf_shapes_paint_materials() {
+    let region = Region::new(Vec3::zero(), Vec3::broadcast(15));
+    let mut volume = RawVolume::new(region.clone());
+
+    let block = Cuboid {
+        centre: Vec3::broadcast(8.0),
+        half_extents: Vec3::broadcast(4.0),
+    };
+    let hole = Sphere {
+        centre: Vec3::zero(),
+        radius: 2.0,
+    }
+    .translate(Vec3::broadcast(8.0));
+
+    generate_sdf(&mut volume, &region, &block.difference(hole), 1u8);
+    generate_sdf(&mut volume, &region, &|p: Vec3<f32>| p.y - 1.0, 2u8);
+
+    assert_eq!(volume.get_voxel_at(4, 4, 4), 1);
+    assert_eq!(volume.get_voxel_at(8, 8, 8), 0);
+    assert_eq!(volume.get_voxel_at(12, 8, 8), 0);
+    assert_eq!(volume.get_voxel_at(3, 0, 3), 2);
+}
@@ -0,0 +1,106 @@
+use gobs::cubic_surface_extractor::extract_cubic_mesh;
+use gobs::heightmap::{Heightmap, Layer, RawFormat};
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::volume::Volume;
+
+const LAYERS: [Layer<u8>; 3] = [
+    Layer {
+        thickness: 1,
+        material: 1,
+    },
+    Layer {
+        thickness: 2,
+        material: 2,
+    },
+    Layer {
+        thickness: 1,
+        material: 3,
+    },
+];
+
+#[test]
+fn read_pgm() {
+    let plain = b"P2\n# a comment\n3 2\n15\n0 5 10\n15 15 0\n";
+    let heightmap = Heightmap::read_pgm(&mut &plain[..]).unwrap();
+    assert_eq!(heightmap.get_width(), 3);
+    assert_eq!(heightmap.get_depth(), 2);
+    assert_eq!(heightmap.get_max_value(), 15);
+    assert_eq!(heightmap.get(2, 0), 10);
+    assert_eq!(heightmap.get(0, 1), 15);
+
+    let mut binary = b"P5 2 1 1000\n".to_vec();
+    binary.extend_from_slice(&[0x03, 0xe8, 0x01, 0xf4]);
+    let heightmap = Heightmap::read_pgm(&mut &binary[..]).unwrap();
+    assert_eq!(heightmap.get(0, 0), 1000);
+    assert_eq!(heightmap.get(1, 0), 500);
+
+    assert!(Heightmap::read_pgm(&mut &b"P6 1 1 255\n\0\0\0"[..]).is_err());
+    assert!(Heightmap::read_pgm(&mut &b"P5 2 2 255\n\0"[..]).is_err());
+
+    // sizes which overflow
+    let huge = format!("P5 {} 2 255\n\0", usize::MAX);
+    assert!(Heightmap::read_pgm(&mut huge.as_bytes()).is_err());
+    let huge = format!("P5 {} 2 1000\n\0", usize::MAX / 2);
+    assert!(Heightmap::read_pgm(&mut huge.as_bytes()).is_err());
+}
+
+#[test]
+fn read_raw() {
+    let bytes = [0x00, 0x01, 0xff, 0xff];
+    let heightmap = Heightmap::read_raw(&mut &bytes[..], 2, 1, RawFormat::U16LittleEndian).unwrap();
+    assert_eq!(heightmap.get(0, 0), 256);
+    assert_eq!(heightmap.get(1, 0), u16::MAX);
+
+    let heightmap = Heightmap::read_raw(&mut &bytes[..], 2, 2, RawFormat::U8).unwrap();
+    assert_eq!(heightmap.get(1, 1), 255);
+    assert!(Heightmap::read_raw(&mut &bytes[..], 2, 2, RawFormat::U16BigEndian).is_err());
+
+    let error = Heightmap::read_raw(
+        &mut &bytes[..],
+        usize::MAX / 2 + 1,
+        1,
+        RawFormat::U16BigEndian,
+    )
+    .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(Heightmap::read_raw(&mut &bytes[..], usize::MAX / 4, 1, RawFormat::U8).is_err());
+}
+
+#[test]
+fn layered_volume() {
+    let heightmap = Heightmap::from_u8(2, 1, &[255, 51]);
+    let volume = heightmap.to_raw_volume(10.0, &LAYERS).unwrap();
+
+    assert_eq!(volume.get_region().get_height(), 10);
+    let column: Vec<u8> = (0..10).map(|y| volume.get_voxel_at(0, y, 0)).collect();
+    assert_eq!(column, vec![3, 3, 3, 3, 3, 3, 3, 2, 2, 1]);
+    let column: Vec<u8> = (0..3).map(|y| volume.get_voxel_at(1, y, 0)).collect();
+    assert_eq!(column, vec![2, 1, 0]);
+
+    let mesh = extract_cubic_mesh(
+        &mut RawVolumeSampler::new(&volume),
+        volume.get_region(),
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(!mesh.vertices().is_empty());
+}
+
+#[test]
+fn smoothing_averages_neighbours() {
+    let heightmap = Heightmap::from_u16(3, 3, &[0, 0, 0, 0, 900, 0, 0, 0, 0]);
+    let smoothed = heightmap.smooth(1);
+
+    assert_eq!(smoothed.get(1, 1), 100);
+    assert_eq!(smoothed.get(0, 0), 100);
+    assert_eq!(heightmap.smooth(0), heightmap);
+}
+
+#[test]
+fn oversized_volumes_are_rejected() {
+    let heightmap = Heightmap::new(2048, 2048, u16::MAX, vec![0; 2048 * 2048]);
+
+    let error = heightmap.to_raw_volume(512.0, &LAYERS).err().unwrap();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,149 @@
+use gobs::cubic_surface_extractor::extract_lit_cubic_mesh;
+use gobs::lighting::{LightVolume, MAX_LIGHT};
+use gobs::raw_volume::RawVolume;
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+const LAMP: u8 = 9;
+
+fn emission(voxel: u8) -> u8 {
+    if voxel == LAMP {
+        12
+    } else {
+        0
+    }
+}
+
+fn create_volume() -> RawVolume<u8> {
+    let mut volume = RawVolume::new(Region::new(Vec3::zero(), Vec3::broadcast(11)));
+
+    // a floor, and a roof over most of it
+    for p in Region::new(Vec3::zero(), Vec3::new(11, 0, 11)).iter() {
+        volume.set_voxel_at(p.x, p.y, p.z, 1).unwrap();
+    }
+    for p in Region::new(Vec3::new(0, 6, 0), Vec3::new(8, 6, 11)).iter() {
+        volume.set_voxel_at(p.x, p.y, p.z, 1).unwrap();
+    }
+
+    volume
+}
+
+fn assert_same_light(a: &LightVolume, b: &LightVolume) {
+    for p in a.get_region().iter() {
+        assert_eq!(
+            (
+                a.get_sunlight(p.x, p.y, p.z),
+                a.get_block_light(p.x, p.y, p.z)
+            ),
+            (
+                b.get_sunlight(p.x, p.y, p.z),
+                b.get_block_light(p.x, p.y, p.z)
+            ),
+            "at {:?}",
+            p
+        );
+    }
+}
+
+#[test]
+fn sunlight() {
+    let volume = create_volume();
+    let mut light = LightVolume::new(volume.get_region().clone());
+    light.calculate(&volume, emission);
+
+    // straight down from the sky, and out from under the edge of the roof
+    assert_eq!(light.get_sunlight(10, 1, 5), MAX_LIGHT);
+    assert_eq!(light.get_sunlight(8, 3, 5), MAX_LIGHT - 1);
+    assert_eq!(light.get_sunlight(5, 3, 5), MAX_LIGHT - 4);
+    assert_eq!(light.get_sunlight(5, 7, 5), MAX_LIGHT);
+
+    // solid voxels are unlit
+    assert_eq!(light.get_sunlight(5, 6, 5), 0);
+    assert_eq!(light.get_block_light(5, 3, 5), 0);
+}
+
+#[test]
+fn block_light() {
+    let mut volume = create_volume();
+    volume.set_voxel_at(2, 1, 2, LAMP).unwrap();
+
+    let mut light = LightVolume::new(volume.get_region().clone());
+    light.calculate(&volume, emission);
+
+    assert_eq!(light.get_block_light(2, 1, 2), 12);
+    assert_eq!(light.get_block_light(3, 1, 2), 11);
+    assert_eq!(light.get_block_light(4, 2, 3), 8);
+    // light does not pass through the floor
+    assert_eq!(light.get_block_light(2, 0, 2), 0);
+    assert_eq!(light.get_light(2, 2, 2), 11);
+}
+
+#[test]
+fn incremental_updates_match_full_calculation() {
+    let mut volume = create_volume();
+    let mut light = LightVolume::new(volume.get_region().clone());
+    light.calculate(&volume, emission);
+
+    let mut seed = 12345u32;
+    let mut random = move |n: u32| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        (seed >> 16) % n
+    };
+
+    for _ in 0..200 {
+        let p = Vec3::new(random(12), random(12), random(12)).map(|c| c as i32);
+        let voxel = match random(4) {
+            0 => 0,
+            1 => LAMP,
+            _ => 1,
+        };
+
+        volume.set_voxel_at(p.x, p.y, p.z, voxel).unwrap();
+        light.update(&volume, p, emission);
+
+        let mut expected = LightVolume::new(volume.get_region().clone());
+        expected.calculate(&volume, emission);
+        assert_same_light(&light, &expected);
+    }
+}
+
+#[test]
+fn lit_cubic_mesh() {
+    let mut volume = create_volume();
+    volume.set_voxel_at(2, 1, 2, LAMP).unwrap();
+
+    let mut light = LightVolume::new(volume.get_region().clone());
+    light.calculate(&volume, emission);
+
+    let mut sampler = RawVolumeSampler::new(&volume);
+    let mesh = extract_lit_cubic_mesh(
+        &mut sampler,
+        volume.get_region(),
+        |x, y, z| light.get_light(x, y, z),
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(mesh.vertices().len() * 6, mesh.indices().len() * 4);
+
+    let light_at = |position: Vec3<u8>| {
+        mesh.vertices()
+            .iter()
+            .filter(|v| v.decode() == position)
+            .map(|v| v.light)
+            .collect::<Vec<_>>()
+    };
+
+    // the top of the floor out in the open, and under the roof
+    assert!(light_at(Vec3::new(11, 1, 5))
+        .iter()
+        .all(|l| *l == MAX_LIGHT));
+    assert_eq!(
+        light_at(Vec3::new(5, 1, 5)).iter().max(),
+        Some(&(MAX_LIGHT - 4))
+    );
+    // the top of the lamp
+    assert!(light_at(Vec3::new(3, 2, 3)).contains(&11));
+}
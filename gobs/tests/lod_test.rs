@@ -0,0 +1,54 @@
+use gobs::lod::{average, downsample, downsample_levels, extract_lod_cubic_meshes, most_common};
+use gobs::raw_volume::RawVolume;
+use gobs::region::Region;
+use gobs::volume::Volume;
+
+fn slab() -> RawVolume<u8> {
+    let mut volume: RawVolume<u8> = RawVolume::new(Region::sized(15, 7, 7));
+    for z in 0..8 {
+        for y in 0..4 {
+            for x in 0..16 {
+                volume.set_voxel_at(x, y, z, 1).unwrap();
+            }
+        }
+    }
+
+    volume
+}
+
+#[test]
+fn most_common_prefers_solid_on_tie() {
+    assert_eq!(most_common(&[0u8, 2, 0, 2]), 2);
+    assert_eq!(most_common(&[0u8, 0, 0, 2]), 0);
+}
+
+#[test]
+fn downsample_densities() {
+    let mut volume: RawVolume<f32> = RawVolume::new(Region::sized(3, 3, 3));
+    volume.set_voxel_at(0, 0, 0, 8.0).unwrap();
+
+    let result = downsample(&volume, 2, average);
+
+    assert_eq!(result.get_region().get_volume(), 8);
+    assert_eq!(result.get_voxel_at(0, 0, 0), 1.0);
+    assert_eq!(result.get_voxel_at(1, 0, 0), 0.0);
+}
+
+#[test]
+fn transition_faces_between_levels() {
+    let volume = slab();
+    let coarse = downsample_levels(&volume, 1, most_common);
+    assert_eq!(coarse[0].get_region().get_width(), 8);
+    assert_eq!(coarse[0].get_voxel_at(7, 1, 3), 1);
+    assert_eq!(coarse[0].get_voxel_at(7, 2, 3), 0);
+
+    let levels = [&volume, &coarse[0]];
+
+    let uniform = extract_lod_cubic_meshes(&levels, 8, |_| 0, None).unwrap();
+    let mixed = extract_lod_cubic_meshes(&levels, 8, |chunk| chunk.x as usize, None).unwrap();
+
+    assert_eq!(mixed.len(), 2);
+    assert_eq!(mixed[1].level, 1);
+    assert!(mixed[0].mesh.indices().len() > uniform[0].mesh.indices().len());
+    assert!(!mixed[1].mesh.is_empty());
+}
@@ -0,0 +1,118 @@
+use gobs::connectivity::Connectivity;
+use gobs::morphology::{close, dilate, erode, fill_interior, hollow, open, StructuringElement};
+use gobs::raw_volume::RawVolume;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+fn create_volume() -> RawVolume<u8> {
+    let mut volume = RawVolume::new(Region::new(Vec3::zero(), Vec3::broadcast(15)));
+
+    for p in Region::new(Vec3::broadcast(4), Vec3::broadcast(10)).iter() {
+        volume.set_voxel_at(p.x, p.y, p.z, 1).unwrap();
+    }
+
+    volume
+}
+
+fn count(volume: &RawVolume<u8>) -> usize {
+    volume.iter_non_empty().count()
+}
+
+#[test]
+fn structuring_elements() {
+    assert_eq!(StructuringElement::Cube(1).offsets().len(), 26);
+    assert_eq!(StructuringElement::Diamond(1).offsets().len(), 6);
+    assert_eq!(StructuringElement::Sphere(2).offsets().len(), 32);
+    assert_eq!(StructuringElement::Sphere(2).radius(), 2);
+    assert_eq!(
+        StructuringElement::Custom(vec![Vec3::new(0, 3, 0), Vec3::zero()]).offsets(),
+        vec![Vec3::new(0, 3, 0)]
+    );
+}
+
+#[test]
+fn dilate_and_erode() {
+    let volume = create_volume();
+    let region = volume.get_region();
+
+    let dilated = dilate(&volume, region, &StructuringElement::Cube(1));
+    assert_eq!(count(&dilated), 9 * 9 * 9);
+    assert_eq!(dilated.get_voxel_at(3, 3, 3), 1);
+
+    let eroded = erode(&volume, region, &StructuringElement::Cube(1));
+    assert_eq!(count(&eroded), 5 * 5 * 5);
+    assert_eq!(eroded.get_voxel_at(4, 7, 7), 0);
+}
+
+#[test]
+fn open_and_close() {
+    let mut volume = create_volume();
+    let region = volume.get_region().clone();
+
+    // a speck, and a thin cavity inside the cube
+    volume.set_voxel_at(1, 1, 1, 2).unwrap();
+    for x in 5..=9 {
+        volume.set_voxel_at(x, 7, 7, 0).unwrap();
+    }
+
+    let element = StructuringElement::Diamond(1);
+    let opened = open(&volume, &region, &element);
+    assert_eq!(opened.get_voxel_at(1, 1, 1), 0);
+    assert_eq!(opened.get_voxel_at(7, 5, 5), 1);
+
+    let closed = close(&volume, &region, &element);
+    assert_eq!(closed.get_voxel_at(7, 7, 7), 1);
+    assert_eq!(closed.get_voxel_at(1, 1, 1), 2);
+    assert_eq!(count(&closed), 7 * 7 * 7 + 1);
+}
+
+#[test]
+fn hollow_and_fill() {
+    let volume = create_volume();
+    let region = volume.get_region();
+
+    let shell = hollow(&volume, region, Connectivity::Six);
+    assert_eq!(count(&shell), 7 * 7 * 7 - 5 * 5 * 5);
+    assert_eq!(shell.get_voxel_at(7, 7, 7), 0);
+
+    let filled = fill_interior(&shell, region, 3);
+    assert_eq!(count(&filled), 7 * 7 * 7);
+    assert_eq!(filled.get_voxel_at(7, 7, 7), 3);
+    assert_eq!(filled.get_voxel_at(4, 4, 4), 1);
+    assert_eq!(filled.get_voxel_at(0, 0, 0), 0);
+}
+
+#[test]
+fn asymmetric_elements() {
+    let mut volume: RawVolume<u8> =
+        RawVolume::new(Region::new(Vec3::broadcast(-4), Vec3::broadcast(4)));
+    volume.set_voxel_at(0, 0, 0, 1).unwrap();
+    volume.set_voxel_at(1, 0, 0, 1).unwrap();
+    volume.set_voxel_at(0, 2, 0, 1).unwrap();
+    let region = volume.get_region().clone();
+
+    let element = StructuringElement::Custom(vec![Vec3::new(1, 0, 0)]);
+
+    let dilated = dilate(&volume, &region, &element);
+    assert_eq!(dilated.get_voxel_at(2, 0, 0), 1);
+    assert_eq!(dilated.get_voxel_at(-1, 0, 0), 0);
+
+    let opened = open(&volume, &region, &element);
+    let closed = close(&volume, &region, &element);
+    for p in region.iter() {
+        let original = volume.get_voxel_at(p.x, p.y, p.z) != 0;
+        if opened.get_voxel_at(p.x, p.y, p.z) != 0 {
+            assert!(original, "opening added {:?}", p);
+        }
+        if original {
+            assert_ne!(
+                closed.get_voxel_at(p.x, p.y, p.z),
+                0,
+                "closing removed {:?}",
+                p
+            );
+        }
+    }
+    assert_eq!(count(&opened), 2);
+}
@@ -0,0 +1,104 @@
+use gobs::cubic_surface_extractor::extract_cubic_mesh;
+use gobs::octree_volume::{extract_octree_cubic_meshes, OctreeVolume};
+use gobs::raw_volume::RawVolume;
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+#[test]
+fn collapses_uniform_subtrees() {
+    let mut volume: OctreeVolume<u8> = OctreeVolume::new(Region::sized(63, 63, 63));
+    assert_eq!(volume.get_node_count(), 1);
+
+    volume.set_voxel_at(10, 20, 30, 3).unwrap();
+    assert_eq!(volume.get_voxel_at(10, 20, 30), 3);
+    assert_eq!(volume.get_voxel_at(11, 20, 30), 0);
+    assert!(volume.get_node_count() > 1);
+
+    volume.set_voxel_at(10, 20, 30, 0).unwrap();
+    assert_eq!(volume.get_node_count(), 1);
+    assert!(volume.set_voxel_at(64, 0, 0, 1).is_err());
+}
+
+#[test]
+fn round_trips_through_raw_volume() {
+    let mut raw: RawVolume<u8> =
+        RawVolume::new(Region::new(Vec3::new(-3, 2, 5), Vec3::new(9, 12, 8)));
+    raw.set_voxel_at(-3, 2, 5, 1).unwrap();
+    raw.set_voxel_at(4, 7, 6, 2).unwrap();
+    raw.set_voxel_at(9, 12, 8, 3).unwrap();
+
+    let octree = OctreeVolume::from_raw_volume(&raw);
+    let copy = octree.to_raw_volume();
+
+    assert_eq!(copy.get_data(), raw.get_data());
+}
+
+#[test]
+fn builds_elongated_volumes() {
+    // the power of two cube around this is 4096 voxels on a side, almost all outside the region
+    let mut raw: RawVolume<u8> = RawVolume::new(Region::new(Vec3::zero(), Vec3::new(4095, 0, 0)));
+    for x in (0..4096).step_by(3) {
+        raw.set_voxel_at(x, 0, 0, 1).unwrap();
+    }
+
+    let octree = OctreeVolume::from_raw_volume(&raw);
+
+    assert_eq!(octree.to_raw_volume().get_data(), raw.get_data());
+}
+
+#[test]
+fn accepts_regions_up_to_the_largest_cube() {
+    let far = (1 << 30) - 1;
+    let mut volume: OctreeVolume<u8> =
+        OctreeVolume::new(Region::new(Vec3::zero(), Vec3::new(far, 0, 0)));
+    volume.set_voxel_at(far, 0, 0, 5).unwrap();
+
+    assert_eq!(volume.get_voxel_at(far, 0, 0), 5);
+    assert_eq!(volume.get_voxel_at(far - 1, 0, 0), 0);
+}
+
+#[test]
+#[should_panic(expected = "at most 2^30 voxels across")]
+fn rejects_regions_wider_than_the_largest_cube() {
+    let _: OctreeVolume<u8> =
+        OctreeVolume::new(Region::new(Vec3::zero(), Vec3::new(1 << 30, 0, 0)));
+}
+
+#[test]
+fn raycast_skips_empty_space() {
+    let mut volume: OctreeVolume<u8> = OctreeVolume::new(Region::sized(127, 127, 127));
+    volume.set_voxel_at(100, 64, 64, 7).unwrap();
+
+    let hit = volume
+        .raycast(Vec3::new(0.5, 64.5, 64.5), Vec3::new(1.0, 0.0, 0.0), 1000.0)
+        .unwrap();
+    assert_eq!(hit.position, Vec3::new(100, 64, 64));
+    assert_eq!(hit.voxel, 7);
+    assert!((hit.distance - 99.5).abs() < 0.001);
+
+    assert!(volume
+        .raycast(Vec3::new(0.5, 10.5, 64.5), Vec3::new(1.0, 0.0, 0.0), 1000.0)
+        .is_none());
+}
+
+#[test]
+fn chunked_extraction_skips_empty_chunks() {
+    let mut raw: RawVolume<u8> = RawVolume::new(Region::sized(63, 63, 63));
+    raw.set_voxel_at(8, 8, 8, 1).unwrap();
+    let octree = OctreeVolume::from_raw_volume(&raw);
+
+    let meshes = extract_octree_cubic_meshes(&octree, 16, None, None).unwrap();
+    let full = extract_cubic_mesh(
+        &mut RawVolumeSampler::new(&raw),
+        raw.get_region(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(meshes.len(), 1);
+    assert_eq!(meshes[0].1.vertices().len(), full.vertices().len());
+    assert_eq!(meshes[0].1.indices().len(), full.indices().len());
+}
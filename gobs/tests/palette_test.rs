@@ -0,0 +1,71 @@
+use gobs::palette::{linear_to_srgb, srgb_to_linear, Palette, Rgba};
+use gobs::raw_volume::RawVolume;
+use gobs::region::Region;
+use gobs::volume::Volume;
+
+#[test]
+fn palette_packing() {
+    let palette = Palette::from_abgr(&[0x00000000, 0xff332211]);
+
+    assert_eq!(palette.get(1), Rgba::new(0x11, 0x22, 0x33, 0xff));
+    assert_eq!(palette.get(1).to_argb(), 0xff112233);
+    assert_eq!(palette.get(1).to_abgr(), 0xff332211);
+    assert_eq!(Rgba::from_argb(0xff112233), palette.get(1));
+    assert_eq!(palette.get(7), Rgba::default());
+}
+
+#[test]
+fn map_indices_to_colours() {
+    let palette = Palette::new(vec![Rgba::default(), Rgba::new(255, 0, 0, 255)]);
+    let mut volume: RawVolume<u8> = RawVolume::new(Region::sized(2, 2, 2));
+    volume.set_voxel_at(1, 1, 1, 1).unwrap();
+
+    let colours = volume.map(|i| palette.get(i as usize).to_argb());
+    assert_eq!(colours.get_voxel_at(1, 1, 1), 0xffff0000u32);
+    assert_eq!(colours.get_voxel_at(0, 1, 1), 0);
+
+    let converted = volume.map_into(|i| i as f32 * 0.5);
+    assert_eq!(converted.get_voxel_at(1, 1, 1), 0.5);
+    assert_eq!(converted.get_region().get_volume(), 27);
+}
+
+#[test]
+fn colour_space_round_trip() {
+    for value in 0..=255u8 {
+        let colour = Rgba::new(value, 255 - value, value / 2, value);
+        assert_eq!(Rgba::from_linear(colour.to_linear()), colour);
+    }
+
+    assert_eq!(srgb_to_linear(255), 1.0);
+    assert!((srgb_to_linear(128) - 0.2158).abs() < 0.001);
+    assert_eq!(linear_to_srgb(0.2158), 128);
+}
+
+#[test]
+fn nearest_colour() {
+    let palette = Palette::new(vec![
+        Rgba::new(0, 0, 0, 255),
+        Rgba::new(255, 0, 0, 255),
+        Rgba::new(0, 0, 255, 255),
+    ]);
+
+    assert_eq!(palette.find_nearest(Rgba::new(200, 30, 20, 255)), Some(1));
+    assert_eq!(palette.find_nearest(Rgba::new(10, 10, 10, 255)), Some(0));
+    assert_eq!(Palette::default().find_nearest(Rgba::default()), None);
+}
+
+#[test]
+fn serialization() {
+    let palette = Palette::new(vec![Rgba::new(1, 2, 3, 4), Rgba::new(250, 251, 252, 253)]);
+
+    let mut bytes = vec![];
+    palette.write(&mut bytes).unwrap();
+    assert_eq!(bytes, vec![1, 2, 3, 4, 250, 251, 252, 253]);
+    assert_eq!(Palette::read(&mut bytes.as_slice()).unwrap(), palette);
+    assert!(Palette::read(&mut [1u8, 2, 3].as_ref()).is_err());
+
+    assert_eq!(palette.get(1).to_hex(), "#fafbfcfd");
+    assert_eq!(Rgba::from_hex("#fafbfcfd"), Some(palette.get(1)));
+    assert_eq!(Rgba::from_hex("010203"), Some(Rgba::new(1, 2, 3, 255)));
+    assert_eq!(Rgba::from_hex("#12345"), None);
+}
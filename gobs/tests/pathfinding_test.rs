@@ -0,0 +1,144 @@
+use gobs::pathfinding::{find_path, is_walkable, PathOptions};
+use gobs::raw_volume::RawVolume;
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+fn create_volume() -> RawVolume<u8> {
+    let mut volume = RawVolume::new(Region::new(Vec3::zero(), Vec3::new(15, 7, 15)));
+
+    // a floor with a wall across it, leaving a gap at z = 15
+    for p in Region::new(Vec3::zero(), Vec3::new(15, 0, 15)).iter() {
+        volume.set_voxel_at(p.x, p.y, p.z, 1).unwrap();
+    }
+    for p in Region::new(Vec3::new(8, 1, 0), Vec3::new(8, 3, 14)).iter() {
+        volume.set_voxel_at(p.x, p.y, p.z, 2).unwrap();
+    }
+
+    // a one voxel step and a two voxel ledge
+    volume.set_voxel_at(2, 1, 14, 3).unwrap();
+    for y in 1..3 {
+        volume.set_voxel_at(4, y, 14, 3).unwrap();
+    }
+
+    // a low ceiling
+    volume.set_voxel_at(12, 2, 12, 4).unwrap();
+
+    volume
+}
+
+#[test]
+fn walkable_cells() {
+    let volume = create_volume();
+    let mut sampler = RawVolumeSampler::new(&volume);
+    let options = PathOptions::default();
+
+    assert!(is_walkable(&mut sampler, Vec3::new(1, 1, 1), &options));
+    assert!(is_walkable(&mut sampler, Vec3::new(2, 2, 14), &options));
+    // in the air, inside the wall and under the low ceiling
+    assert!(!is_walkable(&mut sampler, Vec3::new(1, 2, 1), &options));
+    assert!(!is_walkable(&mut sampler, Vec3::new(8, 1, 1), &options));
+    assert!(!is_walkable(&mut sampler, Vec3::new(12, 1, 12), &options));
+    assert!(is_walkable(
+        &mut sampler,
+        Vec3::new(12, 1, 12),
+        &PathOptions {
+            agent_height: 1,
+            ..options
+        }
+    ));
+}
+
+#[test]
+fn path_around_wall() {
+    let volume = create_volume();
+    let mut sampler = RawVolumeSampler::new(&volume);
+    let options = PathOptions::default();
+
+    let start = Vec3::new(2, 1, 2);
+    let goal = Vec3::new(13, 1, 2);
+    let path = find_path(&mut sampler, start, goal, &options).unwrap();
+
+    assert_eq!(path.first(), Some(&start));
+    assert_eq!(path.last(), Some(&goal));
+    assert!(path.iter().any(|p| p.x == 8 && p.z == 15));
+    for pair in path.windows(2) {
+        let delta = pair[1] - pair[0];
+        assert!(delta.x.abs() <= 1 && delta.z.abs() <= 1 && delta.y.abs() <= 1);
+        assert!(is_walkable(&mut sampler, pair[1], &options));
+    }
+
+    // a wide agent can not fit through the gap
+    let wide = PathOptions {
+        agent_width: 2,
+        ..options
+    };
+    assert!(find_path(&mut sampler, start, goal, &wide).is_none());
+}
+
+#[test]
+fn step_height() {
+    let volume = create_volume();
+    let mut sampler = RawVolumeSampler::new(&volume);
+    let options = PathOptions::default();
+
+    let path = find_path(
+        &mut sampler,
+        Vec3::new(1, 1, 14),
+        Vec3::new(2, 2, 14),
+        &options,
+    )
+    .unwrap();
+    assert_eq!(path.len(), 2);
+
+    // the ledge is too high to climb but can be dropped from
+    let ledge = Vec3::new(4, 3, 14);
+    let below = Vec3::new(5, 1, 14);
+    assert!(find_path(&mut sampler, below, ledge, &options).is_none());
+    assert_eq!(
+        find_path(&mut sampler, ledge, below, &options)
+            .unwrap()
+            .len(),
+        2
+    );
+
+    let climber = PathOptions {
+        step_height: 2,
+        ..options
+    };
+    assert_eq!(
+        find_path(&mut sampler, below, ledge, &climber)
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn unreachable_goal() {
+    let volume = create_volume();
+    let mut sampler = RawVolumeSampler::new(&volume);
+    let options = PathOptions::default();
+
+    // not standing on anything
+    assert!(find_path(
+        &mut sampler,
+        Vec3::new(1, 1, 1),
+        Vec3::new(1, 5, 1),
+        &options
+    )
+    .is_none());
+
+    let limited = PathOptions {
+        max_visited: 4,
+        ..options
+    };
+    assert!(find_path(
+        &mut sampler,
+        Vec3::new(1, 1, 1),
+        Vec3::new(14, 1, 1),
+        &limited
+    )
+    .is_none());
+}
@@ -0,0 +1,91 @@
+use gobs::raw_volume::RawVolume;
+use gobs::region::Region;
+use gobs::resampling::{rotate_nearest, scale_nearest, scale_trilinear};
+use gobs::transform::{rotate_90, Axis};
+use gobs::vek::{Quaternion, Vec3};
+use gobs::volume::Volume;
+
+fn create_volume() -> RawVolume<u8> {
+    let mut volume = RawVolume::new(Region::new(Vec3::new(2, 2, 2), Vec3::new(5, 3, 4)));
+
+    for (p, voxel) in volume.iter_mut() {
+        *voxel = (p.x + p.y * 4 + p.z * 16) as u8;
+    }
+
+    volume
+}
+
+#[test]
+fn nearest_scaling() {
+    let volume = create_volume();
+
+    let doubled = scale_nearest(&volume, Vec3::broadcast(2.0));
+    assert_eq!(doubled.get_region().get_lower_corner(), Vec3::new(2, 2, 2));
+    assert_eq!(doubled.get_region().get_upper_corner(), Vec3::new(9, 5, 7));
+    for p in doubled.get_region().iter() {
+        let from = Vec3::broadcast(2) + (p - 2) / 2;
+        assert_eq!(
+            doubled.get_voxel_at(p.x, p.y, p.z),
+            volume.get_voxel_at(from.x, from.y, from.z)
+        );
+    }
+
+    let halved = scale_nearest(&doubled, Vec3::broadcast(0.5));
+    assert_eq!(halved.get_data(), volume.get_data());
+
+    let squashed = scale_nearest(&volume, Vec3::new(0.5, 0.1, 1.0));
+    assert_eq!(squashed.get_region().get_width(), 2);
+    assert_eq!(squashed.get_region().get_height(), 1);
+    assert_eq!(squashed.get_region().get_depth(), 3);
+}
+
+#[test]
+fn trilinear_scaling() {
+    let mut volume: RawVolume<f32> = RawVolume::new(Region::new(Vec3::zero(), Vec3::new(1, 0, 0)));
+    volume.set_voxel_at(0, 0, 0, 0.0).unwrap();
+    volume.set_voxel_at(1, 0, 0, 1.0).unwrap();
+
+    let scaled = scale_trilinear(&volume, Vec3::new(2.0, 1.0, 1.0));
+    let values: Vec<f32> = scaled.get_data().clone();
+    assert_eq!(values, vec![0.0, 0.25, 0.75, 1.0]);
+
+    // a constant volume stays constant
+    let mut constant: RawVolume<f64> = RawVolume::new(Region::cubic(3));
+    for (_, voxel) in constant.iter_mut() {
+        *voxel = 2.5;
+    }
+    let scaled = scale_trilinear(&constant, Vec3::new(1.5, 0.7, 3.0));
+    assert!(scaled.get_data().iter().all(|v| (*v - 2.5).abs() < 1e-9));
+}
+
+#[test]
+fn arbitrary_rotation() {
+    let volume = create_volume();
+
+    // quarter turns line up exactly with `rotate_90`, apart from where the region ends up
+    let rotated = rotate_nearest(&volume, Quaternion::rotation_y(std::f32::consts::FRAC_PI_2));
+    let expected = rotate_90(&volume, Axis::Y, 1);
+    assert_eq!(
+        rotated.get_region().get_volume(),
+        volume.get_region().get_volume()
+    );
+    assert_eq!(rotated.get_data(), expected.get_data());
+
+    // other angles grow the region, and the corners come from outside the original
+    let mut cube: RawVolume<u8> = RawVolume::new(Region::cubic(9));
+    for (_, voxel) in cube.iter_mut() {
+        *voxel = 1;
+    }
+    let rotated = rotate_nearest(&cube, Quaternion::rotation_z(std::f32::consts::FRAC_PI_4));
+    assert_eq!(rotated.get_region().get_depth(), 10);
+    assert!(rotated.get_region().get_width() > 10);
+    assert_eq!(rotated.get_voxel_at(5, 5, 5), 1);
+    assert_eq!(
+        rotated.get_voxel_at(
+            rotated.get_region().lower_x,
+            rotated.get_region().lower_y,
+            5
+        ),
+        0
+    );
+}
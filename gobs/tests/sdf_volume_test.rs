@@ -0,0 +1,98 @@
+use gobs::raw_volume::RawVolume;
+use gobs::region::Region;
+use gobs::sdf::Sphere;
+use gobs::sdf_volume::{Distance, SdfVolume};
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+fn sphere(centre: f32, radius: f32) -> Sphere {
+    Sphere {
+        centre: Vec3::broadcast(centre),
+        radius,
+    }
+}
+
+#[test]
+fn quantised_distances() {
+    assert_eq!(i8::from_distance(4.0, 4.0), 127);
+    assert_eq!(i8::from_distance(-10.0, 4.0), -127);
+    assert!((i16::from_distance(1.5, 4.0).to_distance(4.0) - 1.5).abs() < 0.001);
+    assert!((i8::from_distance(1.5, 4.0).to_distance(4.0) - 1.5).abs() < 0.05);
+    assert_eq!(f32::from_distance(9.0, 4.0), 4.0);
+}
+
+#[test]
+fn sample_shape() {
+    let region = Region::new(Vec3::zero(), Vec3::broadcast(15));
+    let volume: SdfVolume<i16> = SdfVolume::from_sdf(region, &sphere(8.0, 4.0), 8.0);
+
+    assert!(volume.is_inside(8, 8, 8));
+    assert!(!volume.is_inside(0, 0, 0));
+    assert!((volume.get_distance(8, 8, 8) + 3.134).abs() < 0.01);
+    assert_eq!(volume.get_distance(-5, 0, 0), 8.0);
+}
+
+#[test]
+fn distance_transform_of_occupancy() {
+    let region = Region::new(Vec3::zero(), Vec3::broadcast(9));
+    let mut occupancy: RawVolume<u8> = RawVolume::new(region.clone());
+    for z in 3..=6 {
+        for y in 3..=6 {
+            for x in 3..=6 {
+                occupancy.set_voxel_at(x, y, z, 1).unwrap();
+            }
+        }
+    }
+
+    let volume: SdfVolume<f32> = SdfVolume::from_occupancy(&occupancy, 5.0);
+    assert_eq!(volume.get_distance(3, 3, 3), -0.5);
+    assert_eq!(volume.get_distance(4, 4, 4), -1.5);
+    assert_eq!(volume.get_distance(0, 4, 4), 2.5);
+    assert!((volume.get_distance(1, 1, 4) - (8.0f32.sqrt() - 0.5)).abs() < 0.0001);
+
+    let round_trip = volume.to_raw_volume(1u8);
+    assert_eq!(round_trip.get_data(), occupancy.get_data());
+
+    let mut full: RawVolume<u8> = RawVolume::new(Region::new(Vec3::zero(), Vec3::broadcast(2)));
+    for (_, voxel) in full.iter_mut() {
+        *voxel = 1;
+    }
+    let volume: SdfVolume<f32> = SdfVolume::from_occupancy(&full, 5.0);
+    assert_eq!(volume.get_distance(1, 1, 1), -1.5);
+}
+
+#[test]
+fn csg_operations() {
+    let region = Region::new(Vec3::zero(), Vec3::broadcast(19));
+    let a = Sphere {
+        centre: Vec3::new(7.0, 10.0, 10.0),
+        radius: 4.0,
+    };
+    let b: SdfVolume<i8> = SdfVolume::from_sdf(
+        region.clone(),
+        &Sphere {
+            centre: Vec3::new(13.0, 10.0, 10.0),
+            radius: 4.0,
+        },
+        10.0,
+    );
+
+    let mut union: SdfVolume<f32> = SdfVolume::from_sdf(region.clone(), &a, 10.0);
+    union.union(&b, 0.0);
+    assert!(union.is_inside(6, 10, 10) && union.is_inside(13, 10, 10));
+    assert!(!union.is_inside(9, 13, 10));
+
+    let mut blended: SdfVolume<f32> = SdfVolume::from_sdf(region.clone(), &a, 10.0);
+    blended.union(&b, 4.0);
+    assert!(blended.is_inside(9, 13, 10));
+
+    let mut subtracted: SdfVolume<f32> = SdfVolume::from_sdf(region.clone(), &a, 10.0);
+    subtracted.subtract(&b, 0.0);
+    assert!(subtracted.is_inside(6, 10, 10));
+    assert!(!subtracted.is_inside(9, 10, 10));
+
+    let mut intersected: SdfVolume<f32> = SdfVolume::from_sdf(region, &a, 10.0);
+    intersected.intersect(&b, 0.0);
+    assert!(intersected.is_inside(9, 10, 10));
+    assert!(!intersected.is_inside(6, 10, 10));
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "serde")]
+
+use gobs::cubic_surface_extractor::{extract_cubic_mesh, CubicVertex};
+use gobs::mesh::{FaceArity, Mesh};
+use gobs::raw_volume::RawVolume;
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::vertex::Vertex;
+use gobs::volume::Volume;
+
+fn create_volume() -> RawVolume<u8> {
+    let mut volume = RawVolume::new(Region::new(Vec3::broadcast(-2), Vec3::broadcast(5)));
+    volume.set_border_value(3);
+    for z in 0..3 {
+        for y in 0..3 {
+            for x in 0..3 {
+                volume.set_voxel_at(x, y, z, 1 + (x % 2) as u8).unwrap();
+            }
+        }
+    }
+
+    volume
+}
+
+#[test]
+fn raw_volume_round_trip() {
+    let volume = create_volume();
+    let json = serde_json::to_string(&volume).unwrap();
+    let decoded: RawVolume<u8> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(decoded.get_data(), volume.get_data());
+    assert_eq!(decoded.get_region().get_lower_corner(), Vec3::broadcast(-2));
+    assert_eq!(decoded.get_border_value(), 3);
+
+    // runs of equal voxels are stored once
+    assert!(json.len() < volume.get_data().len());
+}
+
+#[test]
+fn raw_volume_rejects_mismatched_data() {
+    let json = r#"{"region":{"lower_x":0,"lower_y":0,"lower_z":0,"upper_x":1,"upper_y":1,"upper_z":1},"border_value":0,"runs":[[7,1]]}"#;
+    assert!(serde_json::from_str::<RawVolume<u8>>(json).is_err());
+
+    let json = json.replace("[[7,1]]", "[[7,1],[2,0]]");
+    assert!(serde_json::from_str::<RawVolume<u8>>(&json).is_err());
+
+    let json = json.replace("[[7,1],[2,0]]", "[[7,1],[1,0]]");
+    assert!(serde_json::from_str::<RawVolume<u8>>(&json).is_ok());
+}
+
+#[test]
+fn raw_volume_rejects_huge_regions() {
+    // far more voxels than fit in an i32, and enough for the width alone to overflow one
+    for upper in ["2000", "2147483647"] {
+        let json = r#"{"region":{"lower_x":-2147483648,"lower_y":0,"lower_z":0,"upper_x":UPPER,"upper_y":UPPER,"upper_z":UPPER},"border_value":0,"runs":[[1,1]]}"#
+            .replace("UPPER", upper);
+        assert!(serde_json::from_str::<RawVolume<u8>>(&json).is_err());
+    }
+
+    let json = r#"{"region":{"lower_x":0,"lower_y":0,"lower_z":0,"upper_x":1,"upper_y":1,"upper_z":1},"border_value":0,"runs":[[4294967295,1],[4294967295,1]]}"#;
+    assert!(serde_json::from_str::<RawVolume<u8>>(json).is_err());
+}
+
+#[test]
+fn mesh_round_trip() {
+    let volume = create_volume();
+    let mesh = extract_cubic_mesh(
+        &mut RawVolumeSampler::new(&volume),
+        volume.get_region(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    let json = serde_json::to_string(&mesh).unwrap();
+    let decoded: Mesh<CubicVertex<u8>> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(decoded.indices(), mesh.indices());
+    assert_eq!(decoded.offset(), mesh.offset());
+    assert_eq!(decoded.face_arity(), mesh.face_arity());
+    for (a, b) in decoded.vertices().iter().zip(mesh.vertices()) {
+        assert_eq!(a.decode(), b.decode());
+        assert_eq!(a.data, b.data);
+    }
+
+    let vertex = Vertex::new(Vec3::new(1.0, 2.0, 3.0), Vec3::unit_y(), 4u8);
+    let decoded: Vertex<u8> =
+        serde_json::from_str(&serde_json::to_string(&vertex).unwrap()).unwrap();
+    assert_eq!(decoded.position, vertex.position);
+    assert_eq!(decoded.normal, vertex.normal);
+}
+
+#[test]
+fn mesh_rejects_out_of_range_indices() {
+    let mut mesh: Mesh<Vertex<u8>> = Mesh::new(FaceArity::Three);
+    for x in 0..3 {
+        mesh.add_vertex(Vertex::new(
+            Vec3::new(x as f32, 0.0, 0.0),
+            Vec3::unit_y(),
+            1,
+        ));
+    }
+    mesh.add_triangle(0, 1, 2);
+    let json = serde_json::to_string(&mesh).unwrap();
+    assert!(serde_json::from_str::<Mesh<Vertex<u8>>>(&json).is_ok());
+
+    for index in ["3", "-1"] {
+        let json = json.replace(
+            "\"indices\":[0,1,2]",
+            &format!("\"indices\":[0,1,{}]", index),
+        );
+        assert!(serde_json::from_str::<Mesh<Vertex<u8>>>(&json).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use gobs::cubic_surface_extractor::extract_cubic_mesh;
+use gobs::region::Region;
+use gobs::sparse_volume::{SparseVolume, SparseVolumeSampler};
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+#[test]
+fn scattered_voxels() {
+    let mut volume: SparseVolume<u16> = SparseVolume::new();
+    volume.set_voxel_at(-1_000_000, 5, 7, 1).unwrap();
+    volume.set_voxel_at(1_000_000, -5, 7, 2).unwrap();
+    volume.set_voxel_at(1_000_001, -5, 7, 3).unwrap();
+
+    assert_eq!(volume.get_brick_count(), 2);
+    assert_eq!(volume.get_voxel_at(1_000_000, -5, 7), 2);
+    assert_eq!(volume.get_voxel_at(0, 0, 0), 0);
+
+    let mut occupied: Vec<_> = volume.iter_occupied().collect();
+    occupied.sort_by_key(|(p, _)| p.x);
+    assert_eq!(
+        occupied,
+        vec![
+            (Vec3::new(-1_000_000, 5, 7), 1),
+            (Vec3::new(1_000_000, -5, 7), 2),
+            (Vec3::new(1_000_001, -5, 7), 3),
+        ]
+    );
+
+    volume.set_voxel_at(-1_000_000, 5, 7, 0).unwrap();
+    assert_eq!(volume.get_brick_count(), 1);
+    assert_eq!(volume.get_occupied_count(), 2);
+}
+
+#[test]
+fn extract_from_sparse_volume() {
+    let mut volume: SparseVolume<u8> = SparseVolume::new();
+    volume.set_voxel_at(500, 500, 500, 1).unwrap();
+
+    let region = volume.calculate_occupied_region().unwrap();
+    let region = Region::new(region.get_lower_corner(), region.get_upper_corner() + 1);
+    let mesh =
+        extract_cubic_mesh(&mut SparseVolumeSampler::new(&volume), &region, None, None).unwrap();
+
+    assert_eq!(mesh.vertices().len(), 8);
+    assert_eq!(mesh.indices().len(), 36);
+}
+
+#[test]
+fn default_region_dimensions() {
+    let volume: SparseVolume<u8> = SparseVolume::new();
+    let region = volume.get_region();
+
+    assert_eq!(region.get_width(), i32::MAX);
+    assert_eq!(region.get_height(), i32::MAX);
+    assert_eq!(region.get_depth(), i32::MAX);
+}
@@ -0,0 +1,125 @@
+use gobs::mesh::FaceArity;
+use gobs::raw_volume::RawVolume;
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::region::Region;
+use gobs::sdf::{Cuboid, Sdf, Sphere};
+use gobs::surface_nets::{extract_dual_contouring_mesh, extract_surface_nets_mesh};
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+fn density_volume(shape: &dyn Sdf) -> RawVolume<f32> {
+    let region = Region::new(Vec3::zero(), Vec3::broadcast(15));
+    let mut volume = RawVolume::new(region);
+    for (position, voxel) in volume.iter_mut() {
+        *voxel = -shape.distance(position.map(|c| c as f32 + 0.5));
+    }
+    volume.set_border_value(-100.0);
+
+    volume
+}
+
+#[test]
+fn sphere_surface() {
+    let volume = density_volume(&Sphere {
+        centre: Vec3::broadcast(8.0),
+        radius: 5.0,
+    });
+    let mesh = extract_surface_nets_mesh(
+        &mut RawVolumeSampler::new(&volume),
+        volume.get_region(),
+        0.0,
+        Some(FaceArity::Four),
+    )
+    .unwrap();
+
+    assert!(!mesh.vertices().is_empty());
+    for vertex in mesh.vertices() {
+        let outward = vertex.position - Vec3::broadcast(8.0);
+        assert!((outward.magnitude() - 5.0).abs() < 0.2);
+        assert!(vertex.normal.dot(outward.normalized()) > 0.9);
+    }
+
+    for quad in mesh.indices().chunks(4) {
+        let p: Vec<Vec3<f32>> = quad
+            .iter()
+            .map(|i| mesh.vertices()[*i as usize].position)
+            .collect();
+        let normal = (p[1] - p[0]).cross(p[2] - p[0]);
+        assert!(normal.dot(p[0] - Vec3::broadcast(8.0)) > 0.0);
+    }
+}
+
+#[test]
+fn dual_contouring_keeps_corners() {
+    let volume = density_volume(&Cuboid {
+        centre: Vec3::broadcast(8.0),
+        half_extents: Vec3::broadcast(3.5),
+    });
+    let corner = Vec3::broadcast(8.0 + 3.5);
+    let closest = |mesh: &gobs::mesh::Mesh<gobs::vertex::Vertex<f32>>| {
+        mesh.vertices()
+            .iter()
+            .map(|v| (v.position - corner).magnitude())
+            .fold(f32::INFINITY, f32::min)
+    };
+
+    let mut sampler = RawVolumeSampler::new(&volume);
+    let nets = extract_surface_nets_mesh(&mut sampler, volume.get_region(), 0.0, None).unwrap();
+    let dual = extract_dual_contouring_mesh(&mut sampler, volume.get_region(), 0.0, None).unwrap();
+
+    assert_eq!(nets.indices().len(), dual.indices().len());
+    assert!(closest(&dual) < 0.1);
+    assert!(closest(&nets) > 0.5);
+}
+
+#[test]
+fn neighbouring_regions_join() {
+    let volume = density_volume(&Sphere {
+        centre: Vec3::broadcast(8.0),
+        radius: 5.0,
+    });
+    let mut sampler = RawVolumeSampler::new(&volume);
+
+    let whole = extract_surface_nets_mesh(&mut sampler, volume.get_region(), 0.0, None).unwrap();
+    let left = extract_surface_nets_mesh(
+        &mut sampler,
+        &Region::new(Vec3::zero(), Vec3::new(8, 15, 15)),
+        0.0,
+        None,
+    )
+    .unwrap();
+    let right = extract_surface_nets_mesh(
+        &mut sampler,
+        &Region::new(Vec3::new(8, 0, 0), Vec3::broadcast(15)),
+        0.0,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        left.indices().len() + right.indices().len(),
+        whole.indices().len()
+    );
+}
+
+#[test]
+fn nan_densities_do_not_panic() {
+    let mut volume = density_volume(&Sphere {
+        centre: Vec3::broadcast(8.0),
+        radius: 5.0,
+    });
+    for x in 0..16 {
+        volume.set_voxel_at(x, 8, 8, f32::NAN).unwrap();
+    }
+
+    for extract in [extract_surface_nets_mesh, extract_dual_contouring_mesh] {
+        let mesh = extract(
+            &mut RawVolumeSampler::new(&volume),
+            volume.get_region(),
+            0.0,
+            None,
+        )
+        .unwrap();
+        assert!(!mesh.is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+use gobs::cubic_surface_extractor::{extract_textured_cubic_mesh, Face};
+use gobs::raw_volume::RawVolume;
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::region::Region;
+use gobs::texture_atlas::TextureAtlas;
+use gobs::vek::Vec2;
+use gobs::volume::Volume;
+
+#[test]
+fn single_voxel_case() {
+    let region = Region::cubic(16);
+    let mut volume: RawVolume<i32> = RawVolume::new(region);
+    volume.set_voxel_at(8, 8, 8, 1).unwrap();
+
+    let mut atlas = TextureAtlas::new(4, 4);
+    atlas.set_tile(1, 2);
+    atlas.set_face_tile(1, Face::PositiveY, 5);
+
+    let mut sampler = RawVolumeSampler::new(&volume);
+    let mesh =
+        extract_textured_cubic_mesh(&mut sampler, &Region::cubic(16), &atlas, None, None).unwrap();
+
+    assert_eq!(mesh.vertices().len(), 24);
+    assert_eq!(mesh.indices().len(), 36);
+    for vertex in mesh.vertices() {
+        assert!(vertex.uv.x == 0.0 || vertex.uv.x == 1.0);
+        assert!(vertex.uv.y == 0.0 || vertex.uv.y == 1.0);
+        let expected_tile = if vertex.face == Face::PositiveY { 5 } else { 2 };
+        assert_eq!(vertex.tile, expected_tile);
+    }
+}
+
+#[test]
+fn merged_quads_repeat_texture() {
+    let region = Region::cubic(16);
+    let mut volume: RawVolume<i32> = RawVolume::new(region);
+    volume.set_voxel_at(8, 8, 8, 1).unwrap();
+    volume.set_voxel_at(9, 8, 8, 1).unwrap();
+    volume.set_voxel_at(10, 8, 8, 1).unwrap();
+
+    let atlas = TextureAtlas::new(1, 1);
+
+    let mut sampler = RawVolumeSampler::new(&volume);
+    let mesh =
+        extract_textured_cubic_mesh(&mut sampler, &Region::cubic(16), &atlas, None, None).unwrap();
+
+    let max_u = mesh
+        .vertices()
+        .iter()
+        .filter(|v| v.face == Face::PositiveZ)
+        .map(|v| v.uv.x)
+        .fold(0.0, f32::max);
+    assert_eq!(max_u, 3.0);
+}
+
+#[test]
+fn atlas_uv_wraps_into_tile() {
+    let atlas: TextureAtlas<u8> = TextureAtlas::new(2, 2);
+
+    assert_eq!(
+        atlas.get_atlas_uv(3, Vec2::new(0.0, 0.0)),
+        Vec2::new(0.5, 0.5)
+    );
+    assert_eq!(
+        atlas.get_atlas_uv(3, Vec2::new(2.0, 1.0)),
+        Vec2::new(1.0, 1.0)
+    );
+    assert_eq!(
+        atlas.get_atlas_uv(1, Vec2::new(1.5, 0.25)),
+        Vec2::new(0.75, 0.125)
+    );
+}
+
+#[test]
+#[should_panic(expected = "at least one column and one row")]
+fn atlas_rejects_zero_columns() {
+    let _: TextureAtlas<u8> = TextureAtlas::new(0, 2);
+}
+
+#[test]
+#[should_panic(expected = "at least one column and one row")]
+fn atlas_rejects_zero_rows() {
+    let _: TextureAtlas<u8> = TextureAtlas::new(2, 0);
+}
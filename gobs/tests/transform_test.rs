@@ -0,0 +1,97 @@
+use gobs::raw_volume::RawVolume;
+use gobs::region::Region;
+use gobs::transform::{content_region, crop_to_content, mirror, pad, rotate_90, translate, Axis};
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+fn create_volume() -> RawVolume<u8> {
+    let mut volume = RawVolume::new(Region::new(Vec3::new(10, 20, 30), Vec3::new(13, 21, 32)));
+
+    volume.set_voxel_at(13, 20, 30, 1).unwrap();
+    volume.set_voxel_at(11, 21, 31, 2).unwrap();
+
+    volume
+}
+
+fn assert_same(a: &RawVolume<u8>, b: &RawVolume<u8>) {
+    assert_eq!(
+        a.get_region().get_lower_corner(),
+        b.get_region().get_lower_corner()
+    );
+    assert_eq!(
+        a.get_region().get_upper_corner(),
+        b.get_region().get_upper_corner()
+    );
+    assert_eq!(a.get_data(), b.get_data());
+}
+
+#[test]
+fn rotate() {
+    let volume = create_volume();
+
+    let rotated = rotate_90(&volume, Axis::Y, 1);
+    assert_eq!(
+        rotated.get_region().get_lower_corner(),
+        Vec3::new(10, 20, 30)
+    );
+    assert_eq!(
+        rotated.get_region().get_upper_corner(),
+        Vec3::new(12, 21, 33)
+    );
+    // +x turns towards -z
+    assert_eq!(rotated.get_voxel_at(10, 20, 30), 1);
+    assert_eq!(rotated.get_voxel_at(11, 21, 32), 2);
+
+    // +x turns towards +y
+    let rotated = rotate_90(&volume, Axis::Z, 1);
+    assert_eq!(rotated.get_voxel_at(11, 23, 30), 1);
+
+    for axis in [Axis::X, Axis::Y, Axis::Z] {
+        assert_same(&rotate_90(&volume, axis, 4), &volume);
+        assert_same(&rotate_90(&rotate_90(&volume, axis, 3), axis, -3), &volume);
+        assert_same(
+            &rotate_90(&rotate_90(&volume, axis, 1), axis, 1),
+            &rotate_90(&volume, axis, 2),
+        );
+    }
+}
+
+#[test]
+fn mirror_axes() {
+    let volume = create_volume();
+
+    let mirrored = mirror(&volume, Axis::X);
+    assert_eq!(mirrored.get_voxel_at(10, 20, 30), 1);
+    assert_eq!(mirrored.get_voxel_at(12, 21, 31), 2);
+    assert_same(&mirror(&mirrored, Axis::X), &volume);
+
+    let mirrored = mirror(&volume, Axis::Z);
+    assert_eq!(mirrored.get_voxel_at(13, 20, 32), 1);
+}
+
+#[test]
+fn crop_pad_and_translate() {
+    let volume = create_volume();
+
+    let bounds = content_region(&volume).unwrap();
+    assert_eq!(bounds.get_lower_corner(), Vec3::new(11, 20, 30));
+    assert_eq!(bounds.get_upper_corner(), Vec3::new(13, 21, 31));
+    assert!(content_region(&RawVolume::<u8>::new(Region::cubic(3))).is_none());
+
+    let cropped = crop_to_content(&volume).unwrap();
+    assert_eq!(cropped.get_region().get_volume(), 12);
+    assert_eq!(cropped.get_voxel_at(13, 20, 30), 1);
+
+    let mut bordered = create_volume();
+    bordered.set_border_value(7);
+    let padded = pad(&bordered, 2);
+    assert_eq!(padded.get_region().get_lower_corner(), Vec3::new(8, 18, 28));
+    assert_eq!(padded.get_voxel_at(8, 18, 28), 7);
+    assert_eq!(padded.get_voxel_at(11, 21, 31), 2);
+
+    let moved = translate(&volume, Vec3::zero());
+    assert_eq!(moved.get_region().get_upper_corner(), Vec3::new(3, 1, 2));
+    assert_eq!(moved.get_voxel_at(3, 0, 0), 1);
+    assert_eq!(moved.get_voxel_at(1, 1, 1), 2);
+    assert_eq!(moved.get_data(), volume.get_data());
+}
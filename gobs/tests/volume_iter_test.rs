@@ -0,0 +1,51 @@
+use gobs::raw_volume::RawVolume;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+
+#[test]
+fn iterate_all_and_non_empty() {
+    let mut volume: RawVolume<u8> =
+        RawVolume::new(Region::new(Vec3::new(-1, -1, -1), Vec3::new(1, 1, 1)));
+    volume.set_voxel_at(0, 1, -1, 4).unwrap();
+    volume.set_voxel_at(1, 1, 1, 5).unwrap();
+
+    let all: Vec<_> = volume.iter().collect();
+    assert_eq!(all.len(), 27);
+    assert_eq!(all[0], (Vec3::new(-1, -1, -1), 0));
+    assert_eq!(all[1], (Vec3::new(0, -1, -1), 0));
+    assert_eq!(all[26], (Vec3::new(1, 1, 1), 5));
+
+    let non_empty: Vec<_> = volume.iter_non_empty().collect();
+    assert_eq!(
+        non_empty,
+        vec![(Vec3::new(0, 1, -1), 4), (Vec3::new(1, 1, 1), 5)]
+    );
+
+    let sub: Vec<_> = volume
+        .iter_region(&Region::new(Vec3::new(1, 1, 1), Vec3::new(2, 1, 1)))
+        .map(|(_, voxel)| voxel)
+        .collect();
+    assert_eq!(sub, vec![5, 0]);
+}
+
+#[test]
+fn iterate_mutably() {
+    let mut volume: RawVolume<i32> = RawVolume::new(Region::sized(3, 3, 3));
+    for (position, voxel) in volume.iter_mut() {
+        *voxel = position.x + position.y * 10 + position.z * 100;
+    }
+    assert_eq!(volume.get_voxel_at(3, 2, 1), 123);
+
+    let region = Region::new(Vec3::new(1, 2, 3), Vec3::new(5, 2, 3));
+    let mut visited = 0;
+    for (position, voxel) in volume.iter_region_mut(&region) {
+        assert_eq!(*voxel, position.x + position.y * 10 + position.z * 100);
+        *voxel = -1;
+        visited += 1;
+    }
+
+    assert_eq!(visited, 3);
+    assert_eq!(volume.iter().filter(|(_, voxel)| *voxel == -1).count(), 3);
+    assert_eq!(volume.get_voxel_at(0, 2, 3), 320);
+}
@@ -0,0 +1,79 @@
+use gobs::raw_volume::RawVolume;
+use gobs::raw_volume_sampler::RawVolumeSampler;
+use gobs::region::Region;
+use gobs::sampler::Sampler;
+use gobs::volume::Volume;
+use gobs::volume_sampler::VolumeSampler;
+
+fn peek_all(sampler: &dyn Sampler<i32>) -> Vec<i32> {
+    vec![
+        sampler.peek_voxel_1nx1ny1nz(),
+        sampler.peek_voxel_1nx1ny0pz(),
+        sampler.peek_voxel_1nx1ny1pz(),
+        sampler.peek_voxel_1nx0py1nz(),
+        sampler.peek_voxel_1nx0py0pz(),
+        sampler.peek_voxel_1nx0py1pz(),
+        sampler.peek_voxel_1nx1py1nz(),
+        sampler.peek_voxel_1nx1py0pz(),
+        sampler.peek_voxel_1nx1py1pz(),
+        sampler.peek_voxel_0px1ny1nz(),
+        sampler.peek_voxel_0px1ny0pz(),
+        sampler.peek_voxel_0px1ny1pz(),
+        sampler.peek_voxel_0px0py1nz(),
+        sampler.peek_voxel_0px0py0pz(),
+        sampler.peek_voxel_0px0py1pz(),
+        sampler.peek_voxel_0px1py1nz(),
+        sampler.peek_voxel_0px1py0pz(),
+        sampler.peek_voxel_0px1py1pz(),
+        sampler.peek_voxel_1px1ny1nz(),
+        sampler.peek_voxel_1px1ny0pz(),
+        sampler.peek_voxel_1px1ny1pz(),
+        sampler.peek_voxel_1px0py1nz(),
+        sampler.peek_voxel_1px0py0pz(),
+        sampler.peek_voxel_1px0py1pz(),
+        sampler.peek_voxel_1px1py1nz(),
+        sampler.peek_voxel_1px1py0pz(),
+        sampler.peek_voxel_1px1py1pz(),
+    ]
+}
+
+#[test]
+fn matches_raw_volume_sampler() {
+    let mut volume: RawVolume<i32> = RawVolume::new(Region::sized(5, 5, 5));
+    volume.set_border_value(-1);
+    for z in 0..6 {
+        for y in 0..6 {
+            for x in 0..6 {
+                volume.set_voxel_at(x, y, z, x + y * 10 + z * 100).unwrap();
+            }
+        }
+    }
+
+    let mut raw = RawVolumeSampler::new(&volume);
+    let mut generic = VolumeSampler::new(&volume);
+
+    let moves: [fn(&mut dyn Sampler<i32>); 6] = [
+        |s| s.move_positive_x(),
+        |s| s.move_positive_y(),
+        |s| s.move_positive_z(),
+        |s| s.move_negative_x(),
+        |s| s.move_negative_y(),
+        |s| s.move_negative_z(),
+    ];
+
+    for (x, y, z) in [(0, 0, 0), (2, 3, 4), (4, 4, 4)] {
+        raw.set_position(x, y, z);
+        generic.set_position(x, y, z);
+        assert_eq!(peek_all(&raw), peek_all(&generic));
+
+        for movement in moves.iter() {
+            movement(&mut raw);
+            movement(&mut generic);
+
+            assert_eq!(raw.get_position(), generic.get_position());
+            assert_eq!(peek_all(&raw), peek_all(&generic));
+            assert_eq!(raw.fetch_3x3x3(), generic.fetch_3x3x3());
+            assert_eq!(raw.peek(1, -1, 0), raw.peek_voxel_1px1ny0pz());
+        }
+    }
+}
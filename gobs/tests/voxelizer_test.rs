@@ -0,0 +1,104 @@
+use gobs::raw_volume::RawVolume;
+use gobs::region::Region;
+use gobs::vek::Vec3;
+use gobs::volume::Volume;
+use gobs::voxelizer::{fit_to_region, load_obj, voxelize, Triangle, VoxelizeMode};
+
+const CUBE_OBJ: &str = "
+o cube
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0 0 1
+v 1 0 1
+v 1 1 1
+v 0 1 1
+usemtl red
+f 1 4 3 2
+f 5 6 7 8
+f 1 2 6 5
+usemtl blue
+f 4 8 7 3
+f 1 5 8 4
+f 2/1 3/2 7/3 6/4
+";
+
+fn load_cube(lower: f32, upper: f32) -> Vec<Triangle<u8>> {
+    let mut triangles = load_obj(&mut CUBE_OBJ.as_bytes(), |name| match name {
+        Some("red") => 1,
+        Some("blue") => 2,
+        _ => 3,
+    })
+    .unwrap();
+
+    for triangle in triangles.iter_mut() {
+        for vertex in triangle.vertices.iter_mut() {
+            *vertex = *vertex * (upper - lower) + lower;
+        }
+    }
+
+    triangles
+}
+
+#[test]
+fn load_obj_triangulates_faces() {
+    let triangles = load_cube(0.0, 1.0);
+
+    assert_eq!(triangles.len(), 12);
+    assert_eq!(triangles.iter().filter(|t| t.material == 1).count(), 6);
+    assert_eq!(triangles[11].vertices[2], Vec3::new(1.0, 0.0, 1.0));
+    assert!(load_obj(&mut "f 1 2 3".as_bytes(), |_| 1u8).is_err());
+}
+
+#[test]
+fn surface_and_solid() {
+    let triangles = load_cube(2.5, 9.5);
+    let region = Region::sized(11, 11, 11);
+
+    let mut surface: RawVolume<u8> = RawVolume::new(region.clone());
+    voxelize(&triangles, &mut surface, &region, VoxelizeMode::Surface);
+    assert_eq!(surface.iter_non_empty().count(), 8 * 8 * 8 - 6 * 6 * 6);
+
+    let mut solid: RawVolume<u8> = RawVolume::new(region.clone());
+    voxelize(&triangles, &mut solid, &region, VoxelizeMode::Solid);
+    assert_eq!(solid.iter_non_empty().count(), 8 * 8 * 8);
+    assert_eq!(solid.get_voxel_at(1, 5, 5), 0);
+    assert_ne!(solid.get_voxel_at(5, 5, 5), 0);
+}
+
+#[test]
+fn fit_mesh_into_region() {
+    let mut triangles = load_cube(-1.0, 1.0);
+    fit_to_region(&mut triangles, &Region::sized(7, 3, 7));
+
+    let lower = triangles
+        .iter()
+        .flat_map(|t| t.vertices.iter())
+        .fold(Vec3::broadcast(f32::INFINITY), |a, b| {
+            Vec3::partial_min(a, *b)
+        });
+    assert_eq!(lower, Vec3::new(2.0, 0.0, 2.0));
+}
+
+#[test]
+fn non_finite_vertices_are_ignored() {
+    let mut triangles = load_cube(2.5, 9.5);
+    triangles.push(Triangle {
+        vertices: [
+            Vec3::new(0.0, 0.0, f32::NAN),
+            Vec3::new(11.0, 0.0, 1.0),
+            Vec3::new(0.0, 11.0, 1.0),
+        ],
+        material: 4,
+    });
+    let region = Region::sized(11, 11, 11);
+
+    let mut solid: RawVolume<u8> = RawVolume::new(region.clone());
+    voxelize(&triangles, &mut solid, &region, VoxelizeMode::Solid);
+    assert_eq!(solid.iter_non_empty().count(), 8 * 8 * 8);
+
+    let mut surface: RawVolume<u8> = RawVolume::new(region.clone());
+    voxelize(&triangles, &mut surface, &region, VoxelizeMode::Surface);
+    assert_eq!(surface.iter_non_empty().count(), 8 * 8 * 8 - 6 * 6 * 6);
+}